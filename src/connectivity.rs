@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::Board;
+use crate::position::Position;
+
+impl<T> Board<T> {
+    /// Return all positions which are orthogonally connected to the start position
+    /// through fields matching the given predicate, in the order they were reached.
+    ///
+    /// The start position is included if it matches. If it doesn't, the result is empty.
+    pub fn connected_region<F: Fn(Option<&T>) -> bool>(&self, start: Position, member: F) -> Vec<Position> {
+        let is_member = |pos: Position| self.dimension.contains_position(pos) && member(self.values.get(&pos));
+
+        if !is_member(start) {
+            return vec![];
+        }
+
+        let mut region = vec![];
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start);
+        queue.push_back(start);
+
+        while let Some(current) = queue.pop_front() {
+            region.push(current);
+
+            for neighbor in current.orthogonal_neighbors().iter().copied() {
+                if !visited.contains(&neighbor) && is_member(neighbor) {
+                    visited.insert(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        region
+    }
+
+    /// Set the field at the start position and all orthogonally connected fields with the same
+    /// value (or all connected empty fields) to the new value. Return the changed positions in
+    /// the order of #[Board::connected_region]. If the start already holds the new value,
+    /// nothing changes. On a wrapping board the start position wraps, but the filled
+    /// region doesn't cross the edges.
+    pub fn flood_fill_collect(&mut self, start: Position, new_value: T) -> Vec<Position> where T: Clone + PartialEq {
+        let start = self.normalize(start);
+        let old_value = self.values.get(&start).cloned();
+
+        if old_value.as_ref() == Some(&new_value) {
+            return vec![];
+        }
+
+        let region = self.connected_region(start, |val| val == old_value.as_ref());
+        region.iter().for_each(|pos| { self.values.insert(*pos, new_value.clone()); });
+        region
+    }
+
+    /// Assign every occupied field a region id, where orthogonally connected fields
+    /// with equal values share the same id. Ids start at 0 and are assigned in the
+    /// order of #[Board::iter].
+    pub fn label_regions(&self) -> HashMap<Position, usize> where T: PartialEq {
+        let mut labels = HashMap::with_capacity(self.values.len());
+        let mut next_id = 0;
+
+        for (pos, val) in self.iter() {
+            let val = match val {
+                Some(val) if !labels.contains_key(&pos) => val,
+                _ => continue
+            };
+
+            self.connected_region(pos, |other| other == Some(val))
+                .into_iter()
+                .for_each(|member| { labels.insert(member, next_id); });
+            next_id += 1
+        }
+
+        labels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Board;
+    use crate::dimension::Dimension;
+    use crate::position::Position;
+
+    #[test]
+    fn connected_region_works() {
+        let board = Board::from_char_grid("##.\n.#.\n..#", '.');
+
+        let mut region = board.connected_region(Position::new(1, 1), |val| val.is_some());
+        region.sort();
+
+        assert_eq!(vec![
+            Position::new(0, 0),
+            Position::new(1, 0),
+            Position::new(1, 1),
+        ], region);
+    }
+
+    #[test]
+    fn connected_region_isolated_works() {
+        let board = Board::from_char_grid("##.\n.#.\n..#", '.');
+
+        assert_eq!(vec![Position::new(2, 2)], board.connected_region(Position::new(2, 2), |val| val.is_some()));
+    }
+
+    #[test]
+    fn connected_region_no_member_works() {
+        let board = Board::<char>::new(Dimension::new(3, 3));
+
+        assert!(board.connected_region(Position::new(1, 1), |val| val.is_some()).is_empty());
+    }
+
+    /// Cells with the same value which are not connected to the start should stay unchanged.
+    #[test]
+    fn flood_fill_collect_works() {
+        let mut board = Board::from_char_grid("aab\nbab\naba", '.');
+
+        let mut changed = board.flood_fill_collect(Position::new(0, 0), 'x');
+        changed.sort();
+
+        assert_eq!(vec![
+            Position::new(0, 0),
+            Position::new(1, 0),
+            Position::new(1, 1),
+        ], changed);
+        assert!(board == Board::from_char_grid("xxb\nbxb\naba", '.'));
+    }
+
+    #[test]
+    fn flood_fill_collect_same_value_works() {
+        let mut board = Board::from_char_grid("aa\nab", '.');
+
+        assert!(board.flood_fill_collect(Position::new(0, 0), 'a').is_empty());
+        assert!(board == Board::from_char_grid("aa\nab", '.'));
+    }
+
+    /// On a wrapping board, the start position should wrap like in #[Board::get_field].
+    #[test]
+    fn flood_fill_collect_wrapping_works() {
+        let mut board = Board::new_wrapping(Dimension::new(3, 3));
+        board.set_field(Position::new(0, 0), 'a');
+        board.set_field(Position::new(1, 0), 'a');
+
+        let mut changed = board.flood_fill_collect(Position::new(3, 3), 'x');
+        changed.sort();
+
+        assert_eq!(vec![Position::new(0, 0), Position::new(1, 0)], changed);
+        assert_eq!(Some(&'x'), board.get_field(Position::new(1, 0)));
+    }
+
+    /// Separate clusters of the same value should get different ids.
+    #[test]
+    fn label_regions_works() {
+        let board = Board::from_char_grid("aa.\n..b\na.b", '.');
+        let labels = board.label_regions();
+
+        assert_eq!(5, labels.len());
+        assert_eq!(Some(&0), labels.get(&Position::new(0, 0)));
+        assert_eq!(Some(&0), labels.get(&Position::new(1, 0)));
+        assert_eq!(Some(&1), labels.get(&Position::new(0, 2)));
+        assert_eq!(Some(&2), labels.get(&Position::new(2, 1)));
+        assert_eq!(Some(&2), labels.get(&Position::new(2, 2)));
+    }
+}
@@ -1,6 +1,9 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
 use crate::position::Position;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Dimension {
     pub origin: Position,
     pub max: Position,
@@ -14,16 +17,44 @@ impl Dimension {
 
     /// Create a dimension with a custom origin.
     pub fn from_origin(origin: Position, width: usize, height: usize) -> Self {
-        if width == 0 || height == 0 {
-            panic!("Cannot create dimension with zero width or height!")
+        match Self::try_from_origin(origin, width, height) {
+            Ok(dimension) => dimension,
+            Err(_) => panic!("Cannot create dimension with zero width or height!")
         }
+    }
+
+    /// Create a dimension with the default #[Position] (0, 0) as origin.
+    /// Unlike #[Dimension::new], this returns an error instead of panicking.
+    pub fn try_new(width: usize, height: usize) -> Result<Self, DimensionError> {
+        Self::try_from_origin(Position::default(), width, height)
+    }
 
-        Dimension {
-            origin,
-            max: origin + (width - 1, height - 1),
+    /// Create a dimension with a custom origin.
+    /// Unlike #[Dimension::from_origin], this returns an error instead of panicking.
+    pub fn try_from_origin(origin: Position, width: usize, height: usize) -> Result<Self, DimensionError> {
+        match (width, height) {
+            (0, _) => Err(DimensionError::ZeroWidth),
+            (_, 0) => Err(DimensionError::ZeroHeight),
+            _ => Ok(Dimension {
+                origin,
+                max: origin + (width - 1, height - 1),
+            })
         }
     }
 
+    /// Create the smallest dimension which contains all given positions.
+    /// Return None if no positions are given.
+    pub fn from_positions(positions: &[Position]) -> Option<Self> {
+        let (first, rest) = positions.split_first()?;
+
+        let (origin, max) = rest.iter().fold((*first, *first), |(origin, max), pos| (
+            Position::new(origin.x.min(pos.x), origin.y.min(pos.y)),
+            Position::new(max.x.max(pos.x), max.y.max(pos.y))
+        ));
+
+        Some(Dimension { origin, max })
+    }
+
     pub fn width(&self) -> usize {
         (self.max.x - self.origin.x + 1) as usize
     }
@@ -32,6 +63,12 @@ impl Dimension {
         (self.max.y - self.origin.y + 1) as usize
     }
 
+    /// Return a dimension with the same origin, whose width and height are multiplied
+    /// by the given factors. Panics if a factor is zero, like #[Dimension::from_origin].
+    pub fn scale(&self, sx: usize, sy: usize) -> Dimension {
+        Self::from_origin(self.origin, self.width() * sx, self.height() * sy)
+    }
+
     /// Return how many fields a board can hold with this dimension.
     pub fn field_amount(&self) -> usize {
         self.width() * self.height()
@@ -39,20 +76,90 @@ impl Dimension {
 
     /// Return if a given position is covered by this dimension.
     pub fn contains_position(&self, position: Position) -> bool {
-        self.origin <= position && position <= self.max
+        self.origin.x <= position.x && position.x <= self.max.x
+            && self.origin.y <= position.y && position.y <= self.max.y
     }
 
-    /// Resize this dimension if the given #[Position] exceeds its bonds.
-    pub fn resize(&mut self, position: Position) {
-        if position < self.origin {
-            self.origin = position
+    /// Return the row-major index of the given position inside this dimension,
+    /// or None if the position is not contained by it.
+    pub fn index_of(&self, position: Position) -> Option<usize> {
+        match self.contains_position(position) {
+            true => Some((position.y - self.origin.y) as usize * self.width() + (position.x - self.origin.x) as usize),
+            false => None
         }
+    }
 
-        if position > self.max {
-            self.max = position
+    /// Return the position with the given row-major index inside this dimension,
+    /// or None if the index is out of range.
+    pub fn position_at(&self, index: usize) -> Option<Position> {
+        match index < self.field_amount() {
+            true => Some(self.origin + (index % self.width(), index / self.width())),
+            false => None
         }
     }
 
+    /// Return the dimension covered by both this and the other dimension.
+    /// Return None if they don't overlap.
+    pub fn intersection(&self, other: &Dimension) -> Option<Dimension> {
+        let origin = Position::new(self.origin.x.max(other.origin.x), self.origin.y.max(other.origin.y));
+        let max = Position::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+
+        match origin.x <= max.x && origin.y <= max.y {
+            true => Some(Dimension { origin, max }),
+            false => None
+        }
+    }
+
+    /// Split this dimension into the part above and the part starting at the given row.
+    /// Return an error if the split would leave one of the parts empty.
+    pub fn split_horizontal(&self, at: isize) -> Result<(Dimension, Dimension), DimensionError> {
+        match self.origin.y < at && at <= self.max.y {
+            true => Ok((
+                Dimension { origin: self.origin, max: Position::new(self.max.x, at - 1) },
+                Dimension { origin: Position::new(self.origin.x, at), max: self.max },
+            )),
+            false => Err(DimensionError::SplitOutOfRange(at))
+        }
+    }
+
+    /// Split this dimension into the part left of and the part starting at the given column.
+    /// Return an error if the split would leave one of the parts empty.
+    pub fn split_vertical(&self, at: isize) -> Result<(Dimension, Dimension), DimensionError> {
+        match self.origin.x < at && at <= self.max.x {
+            true => Ok((
+                Dimension { origin: self.origin, max: Position::new(at - 1, self.max.y) },
+                Dimension { origin: Position::new(at, self.origin.y), max: self.max },
+            )),
+            false => Err(DimensionError::SplitOutOfRange(at))
+        }
+    }
+
+    /// Return the nearest position inside this dimension, by clamping
+    /// every coordinate of the given position into its bounds.
+    pub fn clamp_position(&self, position: Position) -> Position {
+        Position::new(
+            position.x.clamp(self.origin.x, self.max.x),
+            position.y.clamp(self.origin.y, self.max.y),
+        )
+    }
+
+    /// Map the given position onto this dimension by wrapping it around
+    /// the edges, like on a torus. Positions inside the dimension stay unchanged.
+    pub fn wrap_position(&self, position: Position) -> Position {
+        let relative = position - self.origin;
+
+        self.origin + Position::new(
+            relative.x.rem_euclid(self.width() as isize),
+            relative.y.rem_euclid(self.height() as isize),
+        )
+    }
+
+    /// Resize this dimension if the given #[Position] exceeds its bonds.
+    pub fn resize(&mut self, position: Position) {
+        self.origin = Position::new(self.origin.x.min(position.x), self.origin.y.min(position.y));
+        self.max = Position::new(self.max.x.max(position.x), self.max.y.max(position.y));
+    }
+
     /// Return an iterator over all possible #[Position]s of this dimension.
     ///
     /// The returned positions are in order, starting with self.origin and ending with
@@ -60,8 +167,48 @@ impl Dimension {
     pub fn iter(&self) -> DimensionIterator {
         DimensionIterator::new(self)
     }
+
+    /// Return an iterator over all #[Position]s which are covered by both this dimension
+    /// and the given region, in the same order as #[Dimension::iter].
+    pub fn iter_region(&self, region: Dimension) -> impl Iterator<Item = Position> {
+        self.intersection(&region).into_iter().flat_map(|dimension| dimension.iter())
+    }
+
+    /// Return an iterator over all #[Position]s on the edges of this dimension,
+    /// in the same order as #[Dimension::iter].
+    pub fn perimeter_iter(&self) -> impl Iterator<Item = Position> {
+        let Dimension { origin, max } = *self;
+
+        self.iter().filter(move |pos| pos.x == origin.x || pos.x == max.x || pos.y == origin.y || pos.y == max.y)
+    }
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Dimension(origin={}, size={}x{})", self.origin, self.width(), self.height())
+    }
+}
+
+/// Errors which can occur when creating or splitting a #[Dimension].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DimensionError {
+    ZeroWidth,
+    ZeroHeight,
+    SplitOutOfRange(isize),
 }
 
+impl Display for DimensionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DimensionError::ZeroWidth => write!(f, "Cannot create dimension with zero width!"),
+            DimensionError::ZeroHeight => write!(f, "Cannot create dimension with zero height!"),
+            DimensionError::SplitOutOfRange(at) => write!(f, "Cannot split dimension at {}!", at)
+        }
+    }
+}
+
+impl Error for DimensionError {}
+
 pub struct DimensionIterator {
     origin: Position,
     current_position: Option<Position>,
@@ -88,7 +235,7 @@ impl Iterator for DimensionIterator {
         let next = match self.current_position {
             None => self.origin,
             Some(pos) if pos == self.max_position => return None,
-            Some(Position { x, y }) if y == y_max && x < x_max => Position::new(x + 1, 0),
+            Some(Position { x, y }) if y == y_max && x < x_max => Position::new(x + 1, self.origin.y),
             Some(Position { x, y }) => Position::new(x, y + 1)
         };
 
@@ -96,9 +243,10 @@ impl Iterator for DimensionIterator {
         Some(next)
     }
 }
+
 #[cfg(test)]
 mod tests {
-    use crate::dimension::Dimension;
+    use crate::dimension::{Dimension, DimensionError};
     use crate::position::Position;
 
     #[test]
@@ -118,4 +266,212 @@ mod tests {
             Position::new(2, 2),
         ], positions_in_dimension)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn dimension_iterator_custom_origin_works() {
+        let dimension = Dimension::from_origin(Position::new(-1, 2), 2, 2);
+        let positions_in_dimension = dimension.iter().collect::<Vec<_>>();
+
+        assert_eq!(vec![
+            Position::new(-1, 2),
+            Position::new(-1, 3),
+            Position::new(0, 2),
+            Position::new(0, 3),
+        ], positions_in_dimension)
+    }
+
+    #[test]
+    fn perimeter_iter_works() {
+        let dimension = Dimension::from_origin(Position::new(1, 1), 3, 3);
+        let positions_on_perimeter = dimension.perimeter_iter().collect::<Vec<_>>();
+
+        assert_eq!(8, positions_on_perimeter.len());
+        assert!(!positions_on_perimeter.contains(&Position::new(2, 2)));
+        assert!(positions_on_perimeter.iter().all(|pos| dimension.contains_position(*pos)));
+    }
+
+    #[test]
+    fn display_works() {
+        let dimension = Dimension::from_origin(Position::new(-1, 2), 3, 4);
+        assert_eq!("Dimension(origin=(-1, 2), size=3x4)", dimension.to_string());
+    }
+
+    #[test]
+    fn index_of_works() {
+        let dimension = Dimension::from_origin(Position::new(-1, 2), 3, 2);
+
+        assert_eq!(Some(0), dimension.index_of(Position::new(-1, 2)));
+        assert_eq!(Some(4), dimension.index_of(Position::new(0, 3)));
+        assert_eq!(Some(5), dimension.index_of(Position::new(1, 3)));
+        assert_eq!(None, dimension.index_of(Position::new(2, 3)));
+        assert_eq!(None, dimension.index_of(Position::new(-1, 1)));
+    }
+
+    #[test]
+    fn position_at_works() {
+        let dimension = Dimension::from_origin(Position::new(-1, 2), 3, 2);
+
+        assert_eq!(Some(Position::new(-1, 2)), dimension.position_at(0));
+        assert_eq!(Some(Position::new(0, 3)), dimension.position_at(4));
+        assert_eq!(None, dimension.position_at(6));
+        assert!(dimension.iter().all(|pos| dimension.position_at(dimension.index_of(pos).unwrap()) == Some(pos)));
+    }
+
+    #[test]
+    fn intersection_works() {
+        let dimension = Dimension::new(4, 4);
+
+        assert_eq!(
+            Some(Dimension::from_origin(Position::new(2, 1), 2, 3)),
+            dimension.intersection(&Dimension::from_origin(Position::new(2, 1), 5, 5))
+        );
+        assert_eq!(None, dimension.intersection(&Dimension::from_origin(Position::new(4, 0), 2, 2)));
+    }
+
+    #[test]
+    fn iter_region_contained_works() {
+        let dimension = Dimension::new(4, 4);
+        let region = Dimension::from_origin(Position::new(1, 1), 2, 2);
+
+        assert_eq!(region.iter().collect::<Vec<_>>(), dimension.iter_region(region).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iter_region_clipped_works() {
+        let dimension = Dimension::new(3, 3);
+        let region = Dimension::from_origin(Position::new(-1, 1), 2, 4);
+
+        assert_eq!(vec![
+            Position::new(0, 1),
+            Position::new(0, 2),
+        ], dimension.iter_region(region).collect::<Vec<_>>());
+        assert_eq!(0, dimension.iter_region(Dimension::from_origin(Position::new(5, 5), 1, 1)).count());
+    }
+
+    #[test]
+    fn split_horizontal_works() {
+        let (top, bottom) = Dimension::new(4, 4).split_horizontal(2).unwrap();
+
+        assert_eq!(Dimension::new(4, 2), top);
+        assert_eq!(Dimension::from_origin(Position::new(0, 2), 4, 2), bottom);
+    }
+
+    #[test]
+    fn split_vertical_works() {
+        let (left, right) = Dimension::new(4, 4).split_vertical(2).unwrap();
+
+        assert_eq!(Dimension::new(2, 4), left);
+        assert_eq!(Dimension::from_origin(Position::new(2, 0), 2, 4), right);
+    }
+
+    #[test]
+    fn split_out_of_range_fails() {
+        let dimension = Dimension::new(4, 4);
+
+        assert_eq!(Err(DimensionError::SplitOutOfRange(0)), dimension.split_horizontal(0));
+        assert_eq!(Err(DimensionError::SplitOutOfRange(4)), dimension.split_vertical(4));
+    }
+
+    #[test]
+    fn try_new_zero_width_fails() {
+        assert_eq!(Err(DimensionError::ZeroWidth), Dimension::try_new(0, 3))
+    }
+
+    #[test]
+    fn try_new_zero_height_fails() {
+        assert_eq!(Err(DimensionError::ZeroHeight), Dimension::try_new(3, 0))
+    }
+
+    #[test]
+    fn try_from_origin_works() {
+        let origin = Position::new(-1, 2);
+        assert_eq!(Ok(Dimension::from_origin(origin, 3, 4)), Dimension::try_from_origin(origin, 3, 4))
+    }
+
+    /// The position must be checked per axis, not by the lexicographic order of #[Position].
+    #[test]
+    fn contains_position_works() {
+        let dimension = Dimension::new(3, 3);
+
+        assert!(dimension.contains_position(Position::new(0, 0)));
+        assert!(dimension.contains_position(Position::new(2, 2)));
+        assert!(!dimension.contains_position(Position::new(1, 3)));
+        assert!(!dimension.contains_position(Position::new(0, -1)));
+        assert!(!dimension.contains_position(Position::new(3, 0)));
+    }
+
+    /// Every axis should be resized on its own.
+    #[test]
+    fn resize_works() {
+        let mut dimension = Dimension::new(3, 3);
+
+        dimension.resize(Position::new(-1, 4));
+        assert_eq!(Dimension::from_origin(Position::new(-1, 0), 4, 5), dimension);
+
+        dimension.resize(Position::new(1, 1));
+        assert_eq!(Dimension::from_origin(Position::new(-1, 0), 4, 5), dimension);
+    }
+
+    #[test]
+    fn clamp_position_works() {
+        let dimension = Dimension::from_origin(Position::new(1, 1), 3, 3);
+
+        assert_eq!(Position::new(2, 2), dimension.clamp_position(Position::new(2, 2)));
+        assert_eq!(Position::new(1, 2), dimension.clamp_position(Position::new(-5, 2)));
+        assert_eq!(Position::new(3, 2), dimension.clamp_position(Position::new(7, 2)));
+        assert_eq!(Position::new(2, 1), dimension.clamp_position(Position::new(2, 0)));
+        assert_eq!(Position::new(2, 3), dimension.clamp_position(Position::new(2, 4)));
+        assert_eq!(Position::new(3, 1), dimension.clamp_position(Position::new(10, -10)));
+    }
+
+    #[test]
+    fn wrap_position_works() {
+        let dimension = Dimension::from_origin(Position::new(1, 1), 3, 3);
+
+        assert_eq!(Position::new(2, 2), dimension.wrap_position(Position::new(2, 2)));
+        assert_eq!(Position::new(1, 2), dimension.wrap_position(Position::new(4, 2)));
+        assert_eq!(Position::new(3, 3), dimension.wrap_position(Position::new(0, 0)));
+        assert_eq!(Position::new(2, 1), dimension.wrap_position(Position::new(-4, 7)));
+    }
+
+    #[test]
+    fn scale_works() {
+        let origin = Position::new(1, -1);
+        let scaled = Dimension::from_origin(origin, 2, 3).scale(2, 1);
+
+        assert_eq!(origin, scaled.origin);
+        assert_eq!(4, scaled.width());
+        assert_eq!(3, scaled.height());
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_zero_fails() {
+        Dimension::new(2, 3).scale(0, 1);
+    }
+
+    #[test]
+    fn from_positions_single_works() {
+        let position = Position::new(2, -1);
+        assert_eq!(Some(Dimension::from_origin(position, 1, 1)), Dimension::from_positions(&[position]))
+    }
+
+    #[test]
+    fn from_positions_works() {
+        let positions = [
+            Position::new(1, 5),
+            Position::new(-2, 3),
+            Position::new(4, 0),
+        ];
+
+        assert_eq!(
+            Some(Dimension::from_origin(Position::new(-2, 0), 7, 6)),
+            Dimension::from_positions(&positions)
+        )
+    }
+
+    #[test]
+    fn from_positions_empty_works() {
+        assert_eq!(None, Dimension::from_positions(&[]))
+    }
+}
@@ -0,0 +1,29 @@
+/// The four orthogonal directions on a board. The y-axis points downwards,
+/// so row 0 is at the top of the board.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Return the position delta of one step in this direction.
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0)
+        }
+    }
+}
+
+/// The two center axes of a board. Mirroring across the vertical axis swaps
+/// left and right, mirroring across the horizontal axis swaps top and bottom.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
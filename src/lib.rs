@@ -1,14 +1,34 @@
 use std::cmp::max;
 use std::collections::HashMap;
+use std::collections::hash_map::{DefaultHasher, IntoIter};
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::iter::{FromIterator, once, successors};
 
 use crate::position::Position;
 use crate::dimension::{Dimension, DimensionIterator};
+use crate::direction::{Axis, Direction};
 
-mod position;
-mod dimension;
+pub mod position;
+pub mod dimension;
+pub mod direction;
+mod connectivity;
+mod pathfinding;
 
-struct Board<T> {
+/// Bit of the side towards (x, y - 1) in the result of #[Board::boundary_sides].
+pub const SIDE_NORTH: u8 = 0b0001;
+/// Bit of the side towards (x + 1, y) in the result of #[Board::boundary_sides].
+pub const SIDE_EAST: u8 = 0b0010;
+/// Bit of the side towards (x, y + 1) in the result of #[Board::boundary_sides].
+pub const SIDE_SOUTH: u8 = 0b0100;
+/// Bit of the side towards (x - 1, y) in the result of #[Board::boundary_sides].
+pub const SIDE_WEST: u8 = 0b1000;
+
+pub struct Board<T> {
     resizeable: bool,
+    wrapping: bool,
     dimension: Dimension,
     values: HashMap<Position, T>,
 }
@@ -17,6 +37,7 @@ impl<T> Board<T> {
     pub fn new(dimension: Dimension) -> Self {
         Board {
             resizeable: false,
+            wrapping: false,
             values: HashMap::with_capacity(dimension.field_amount()),
             dimension,
         }
@@ -25,254 +46,2846 @@ impl<T> Board<T> {
     pub fn new_resizeable(dimension: Dimension) -> Self {
         Board {
             resizeable: true,
+            wrapping: false,
+            values: HashMap::with_capacity(dimension.field_amount()),
+            dimension,
+        }
+    }
+
+    /// Create a board whose edges wrap around, like on a torus. Every position
+    /// outside the dimension is mapped onto the field on the opposite edge.
+    pub fn new_wrapping(dimension: Dimension) -> Self {
+        Board {
+            resizeable: false,
+            wrapping: true,
             values: HashMap::with_capacity(dimension.field_amount()),
             dimension,
         }
     }
 
+    /// Create a not resizeable board where every field holds a clone of the given value.
+    pub fn new_filled(dimension: Dimension, value: T) -> Self where T: Clone {
+        let mut board = Board::new(dimension);
+        board.values.extend(dimension.iter().map(|pos| (pos, value.clone())));
+        board
+    }
+
+    /// Create a not resizeable board where every field is computed from its position.
+    /// Fields for which the closure returns None stay empty.
+    pub fn from_fn<F: Fn(Position) -> Option<T>>(dimension: Dimension, f: F) -> Self {
+        let mut board = Board::new(dimension);
+        board.values.extend(dimension.iter().filter_map(|pos| f(pos).map(|val| (pos, val))));
+        board
+    }
+
+    /// Create a board from nested rows, where `rows[y][x]` becomes the field (x, y).
+    /// The width of the board is the length of the longest row and it is at least 1x1 in size.
+    pub fn from_rows(rows: Vec<Vec<Option<T>>>) -> Self {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut board = Board::new(Dimension::new(max(width, 1), max(rows.len(), 1)));
+
+        for (y, row) in rows.into_iter().enumerate() {
+            row.into_iter()
+                .enumerate()
+                .filter_map(|(x, val_opt)| val_opt.map(|val| (Position::new_u(x, y), val)))
+                .for_each(|(pos, val)| board.set_field(pos, val))
+        }
+
+        board
+    }
+
+    /// Parse a board from comma separated lines, where every line is a row like in #[Board::from_rows].
+    /// Cells for which the closure returns None become empty fields.
+    ///
+    /// Lines may end with "\n" or "\r\n". The board always has its origin at (0, 0),
+    /// as the origin of a board written by #[Board::to_csv] is not stored.
+    pub fn from_csv<F: Fn(&str) -> Option<T>>(s: &str, parse: F) -> Self {
+        Self::from_rows(s.lines()
+            .map(|line| line.split(',').map(&parse).collect())
+            .collect())
+    }
+
+    pub fn is_resizeable(&self) -> bool {
+        self.resizeable
+    }
+
+    /// Set if this board grows when a field outside its dimension gets set.
+    /// Turning this off locks the current dimension.
+    pub fn set_resizeable(&mut self, resizeable: bool) {
+        self.resizeable = resizeable
+    }
+
+    /// Return how many fields of this board are occupied.
+    pub fn count(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Return if every field of this board is occupied. A resizeable
+    /// board is checked against its current dimension.
+    pub fn is_full(&self) -> bool {
+        self.count() == self.dimension.field_amount()
+    }
+
+    /// Return how many fields inside the given region of this board are occupied.
+    pub fn count_in_region(&self, region: Dimension) -> usize {
+        self.dimension.iter_region(region)
+            .filter(|pos| self.values.contains_key(pos))
+            .count()
+    }
+
+    /// Return how many fields of every row of this board are occupied, from top to bottom.
+    pub fn row_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.dimension.height()];
+        self.values.keys()
+            .filter(|pos| self.dimension.contains_position(**pos))
+            .for_each(|pos| counts[(pos.y - self.dimension.origin.y) as usize] += 1);
+        counts
+    }
+
+    /// Return how many fields of every column of this board are occupied, from left to right.
+    pub fn column_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.dimension.width()];
+        self.values.keys()
+            .filter(|pos| self.dimension.contains_position(**pos))
+            .for_each(|pos| counts[(pos.x - self.dimension.origin.x) as usize] += 1);
+        counts
+    }
+
+    /// Clear every row of this board which is fully occupied and let the rows above
+    /// drop down to fill the gaps. Return how many rows were cleared.
+    pub fn clear_full_rows(&mut self) -> usize {
+        let Dimension { origin, max } = self.dimension;
+        let width = self.dimension.width();
+        let counts = self.row_counts();
+        let mut cleared = 0;
+
+        for y in (origin.y..=max.y).rev() {
+            let full = counts[(y - origin.y) as usize] == width;
+
+            for x in origin.x..=max.x {
+                let val_opt = self.values.remove(&Position::new(x, y));
+
+                if let (false, Some(val)) = (full, val_opt) {
+                    self.values.insert(Position::new(x, y + cleared), val);
+                }
+            }
+
+            if full {
+                cleared += 1
+            }
+        }
+
+        cleared as usize
+    }
+
+    /// Return how many values this board can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Reserve capacity for at least 'additional' more values.
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional)
+    }
+
+    /// Remove all values from this board. The capacity is kept.
     pub fn clear(&mut self) {
         self.values.clear()
     }
 
     pub fn get_field(&self, position: Position) -> Option<&T> {
-        self.values.get(&position)
+        self.values.get(&self.normalize(position))
+    }
+
+    /// Return the value of the given field, or the given default if the field is empty.
+    pub fn get_or<'a>(&'a self, position: Position, default: &'a T) -> &'a T {
+        self.get_field(position).unwrap_or(default)
+    }
+
+    /// Return the value of the given field, or the default value of T if the field
+    /// is empty. Return None if the position is not on this board.
+    pub fn get_field_or_default(&self, position: Position) -> Option<T> where T: Default + Clone {
+        let position = self.normalize(position);
+
+        match self.dimension.contains_position(position) {
+            true => Some(self.values.get(&position).cloned().unwrap_or_default()),
+            false => None
+        }
     }
 
     pub fn set_field(&mut self, position: Position, value: T) {
+        let _ = self.try_set_field(position, value);
+    }
+
+    /// Set the given field and return its previous value. Unlike #[Board::set_field],
+    /// this returns an error if the position is outside of a not resizeable board.
+    pub fn try_set_field(&mut self, position: Position, value: T) -> Result<Option<T>, OutOfBounds> {
+        let position = self.normalize(position);
+
         match (self.resizeable, self.dimension.contains_position(position)) {
-            (_, true) => { self.values.insert(position, value); }
+            (_, true) => Ok(self.values.insert(position, value)),
             (true, false) => {
                 self.dimension.resize(position);
-                self.values.insert(position, value);
+                Ok(self.values.insert(position, value))
             }
-            _ => return
+            _ => Err(OutOfBounds(position))
         }
     }
 
-    pub fn clear_field(&mut self, position: Position) -> Option<T> {
-        self.values.remove(&position)
+    /// Set the given field only if it is empty, following the same rules as #[Board::set_field].
+    /// Return if the value was set.
+    pub fn set_if_empty(&mut self, position: Position, value: T) -> bool {
+        match self.get_field(position) {
+            Some(_) => false,
+            None => self.try_set_field(position, value).is_ok()
+        }
     }
 
-    pub fn iter(&self) -> BoardIter<'_, T> {
-        BoardIter {
-            dimension_iter: self.dimension.iter(),
-            values: &self.values,
+    /// Return mutable references to the values of all given fields at once.
+    /// Return None if any field is empty or any two positions are equal.
+    pub fn get_disjoint_mut<const N: usize>(&mut self, positions: [Position; N]) -> Option<[&mut T; N]> {
+        let positions = positions.map(|pos| self.normalize(pos));
+
+        if (0..N).any(|i| positions[(i + 1)..].contains(&positions[i])) {
+            return None;
         }
+
+        let values = self.values.get_disjoint_mut(positions.each_ref());
+        IntoIterator::into_iter(values)
+            .collect::<Option<Vec<_>>>()?
+            .try_into()
+            .ok()
     }
-}
 
-impl<T> Board<T> where T: ToString {
-    /// Print a debug-representation of this board.
-    /// This method exists to provide a default print method
-    /// without overriding #[std::fmt::Display].
-    ///
-    /// The parameter 'empty' determines what should be displayed if
-    /// some board field is empty.
-    pub fn print(&self, empty: &str) {
-        let field_strings = self.iter()
-            .map(|(_, val_opt)| match val_opt {
-                None => empty.to_string(),
-                Some(val) => val.to_string()
-            })
-            .collect::<Vec<_>>();
+    /// Set every field on the line between the given positions (both inclusive)
+    /// to the given value, following the same rules as #[Board::set_field].
+    pub fn draw_line(&mut self, from: Position, to: Position, value: T) where T: Clone {
+        from.line_to(to).for_each(|pos| self.set_field(pos, value.clone()))
+    }
 
-        let cell_size = Self::calculate_cell_size(empty, &self.dimension, &field_strings);
+    /// Set the fields of the given region to the given value, following the same rules
+    /// as #[Board::set_field]. If 'filled' is false, only the edges of the region are set.
+    pub fn draw_rect(&mut self, region: Dimension, value: T, filled: bool) where T: Clone {
+        match filled {
+            true => region.iter().for_each(|pos| self.set_field(pos, value.clone())),
+            false => region.perimeter_iter().for_each(|pos| self.set_field(pos, value.clone()))
+        }
+    }
 
-        let width = self.dimension.width();
-        Self::print_width_indexes(width, cell_size.0);
-        for i in 0..self.dimension.height() {
-            Self::print_row(&i.to_string(), cell_size, &field_strings[(i * width)..(i * width + width)])
+    /// Set every field in the part of the given region which lies on this board to the given value.
+    /// Unlike #[Board::draw_rect], this never resizes the board.
+    pub fn fill_region(&mut self, region: Dimension, value: T) where T: Clone {
+        let dimension = self.dimension;
+        self.values.extend(dimension.iter_region(region).map(|pos| (pos, value.clone())))
+    }
+
+    /// Clear every field in the part of the given region which lies on this board.
+    /// Return how many occupied fields were cleared.
+    pub fn clear_region(&mut self, region: Dimension) -> usize {
+        let dimension = self.dimension;
+        dimension.iter_region(region).filter(|pos| self.values.remove(pos).is_some()).count()
+    }
+
+    /// Apply the given changes to this board. Some value sets the field, following the
+    /// same rules as #[Board::set_field], and None clears it.
+    pub fn apply_changes<I: IntoIterator<Item = (Position, Option<T>)>>(&mut self, changes: I) {
+        for (pos, val_opt) in changes {
+            match val_opt {
+                Some(val) => self.set_field(pos, val),
+                None => { self.clear_field(pos); }
+            }
         }
-        Self::print_width_indexes(width, cell_size.0);
     }
 
-    fn calculate_cell_size(empty: &str, dimension: &Dimension, field_strings: &[String]) -> (usize, usize) {
-        let mut cell_size = (0, 0);
-        let mut update_size = |string: &str| cell_size = (max(cell_size.0, string.len()), max(cell_size.1, string.lines().count()));
+    pub fn clear_field(&mut self, position: Position) -> Option<T> {
+        self.values.remove(&self.normalize(position))
+    }
 
-        update_size(empty);
-        update_size(&dimension.width().to_string());
-        update_size(&dimension.height().to_string());
-        field_strings.iter().for_each(|string| update_size(string));
+    /// Remove the value of the given field and return it. This is the same as #[Board::clear_field].
+    pub fn take(&mut self, position: Position) -> Option<T> {
+        self.clear_field(position)
+    }
 
-        cell_size
+    /// Set the given field, following the same rules as #[Board::set_field], and return its
+    /// previous value. Return None if the field was empty or the value could not be set.
+    pub fn replace(&mut self, position: Position, value: T) -> Option<T> {
+        self.try_set_field(position, value).ok().flatten()
     }
 
-    fn print_width_indexes(width: usize, cell_width: usize) {
-        let mut line = String::new();
-        line.push_str(Self::whitespace(cell_width + 1).as_str());
+    /// Move every value of this board by the given delta. Values which would
+    /// leave the dimension get dropped, the dimension itself stays unchanged.
+    pub fn shift(&mut self, delta: (isize, isize)) where T: Clone {
+        let dimension = self.dimension;
 
-        for i in 0..width {
-            let index_string = i.to_string();
-            line.push_str(&index_string);
-            line.push_str(&Self::whitespace(cell_width.checked_sub(index_string.len()).unwrap_or(0) + 1));
-        }
+        self.values = self.values.drain()
+            .map(|(pos, val)| (pos + delta, val))
+            .filter(|(pos, _)| dimension.contains_position(*pos))
+            .collect()
+    }
 
-        println!("{}", line);
+    /// Move every value of this board by the given delta. Values which leave the
+    /// dimension on one edge reappear on the opposite one, so nothing gets lost.
+    pub fn shift_wrapping(&mut self, delta: (isize, isize)) {
+        let dimension = self.dimension;
+
+        self.values = self.values.drain()
+            .map(|(pos, val)| (dimension.wrap_position(pos + delta), val))
+            .collect()
     }
 
-    fn print_row(index_string: &str, cell_size: (usize, usize), strings: &[String]) {
-        let mut row_lines = vec![vec![]; cell_size.1];
-        for string in strings {
-            let mut lines = string.lines();
-            (0..cell_size.1)
-                .into_iter()
-                .for_each(|i| row_lines[i].push(lines.next().unwrap_or("")));
+    /// Let all values of this board fall in the given direction. In every column (or row)
+    /// along the direction, the values get packed against the edge of the board while
+    /// keeping their order. The remaining fields become empty.
+    pub fn apply_gravity(&mut self, direction: Direction) {
+        let dimension = self.dimension;
+        let delta = direction.delta();
+        let back = (-delta.0, -delta.1);
+        let edge = dimension.iter()
+            .filter(|pos| !dimension.contains_position(pos + delta))
+            .collect::<Vec<_>>();
+
+        for start in edge {
+            let lane = successors(Some(start), |pos| Some(pos + back))
+                .take_while(|pos| dimension.contains_position(*pos))
+                .collect::<Vec<_>>();
+            let values = lane.iter()
+                .filter_map(|pos| self.values.remove(pos))
+                .collect::<Vec<_>>();
+
+            self.values.extend(lane.into_iter().zip(values))
         }
+    }
 
-        for (i, field_lines) in row_lines.iter().enumerate() {
-            let mut line_string = String::new();
+    /// Fill this board by repeating the given pattern, starting at the origin of both boards.
+    /// Fields whose corresponding pattern field is empty become empty as well.
+    pub fn tile_from(&mut self, pattern: &Board<T>) where T: Clone {
+        let origin = self.dimension.origin;
+        let pattern_dimension = pattern.dimension;
 
-            for line in field_lines {
-                line_string.push_str(line);
-                line_string.push_str(&Self::whitespace(cell_size.0 + 1 - line.len()));
-            }
+        self.values = self.dimension.iter()
+            .filter_map(|pos| {
+                let pattern_pos = pattern_dimension.wrap_position(pattern_dimension.origin + (pos - origin));
+                pattern.values.get(&pattern_pos).map(|val| (pos, val.clone()))
+            })
+            .collect()
+    }
 
-            line_string = match i {
-                0 => format!(
-                    "{}{}{}{}",
-                    index_string,
-                    &Self::whitespace(cell_size.0 + 1 - index_string.len()),
-                    line_string,
-                    index_string
-                ),
-                _ => format!(
-                    "{}{}",
-                    &Self::whitespace(cell_size.0 + 1),
-                    line_string
-                )
-            };
+    /// Exchange the contents (including empty fields) of the rows y1 and y2.
+    /// Nothing happens if any of the rows is outside of this board.
+    pub fn swap_rows(&mut self, y1: isize, y2: isize) {
+        let Dimension { origin, max } = self.dimension;
 
-            println!("{}", line_string);
+        if (origin.y..=max.y).contains(&y1) && (origin.y..=max.y).contains(&y2) {
+            (origin.x..=max.x).for_each(|x| self.swap_fields(Position::new(x, y1), Position::new(x, y2)))
         }
     }
 
-    fn whitespace(length: usize) -> String {
-        (0..length).map(|_| String::from(" ")).collect()
+    /// Exchange the contents (including empty fields) of the columns x1 and x2.
+    /// Nothing happens if any of the columns is outside of this board.
+    pub fn swap_columns(&mut self, x1: isize, x2: isize) {
+        let Dimension { origin, max } = self.dimension;
+
+        if (origin.x..=max.x).contains(&x1) && (origin.x..=max.x).contains(&x2) {
+            (origin.y..=max.y).for_each(|y| self.swap_fields(Position::new(x1, y), Position::new(x2, y)))
+        }
     }
-}
 
-/// Iterator over all board-positions with their current value.
-/// The item-type is (Position, Option<&'a T>). The positions
-/// are always in order.
-struct BoardIter<'a, T> {
-    dimension_iter: DimensionIterator,
-    values: &'a HashMap<Position, T>,
-}
+    fn swap_fields(&mut self, a: Position, b: Position) {
+        let val_a = self.values.remove(&a);
+        let val_b = self.values.remove(&b);
 
-impl<'a, T> Iterator for BoardIter<'a, T> {
-    type Item = (Position, Option<&'a T>);
+        if let Some(val) = val_a {
+            self.values.insert(b, val);
+        }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.dimension_iter.next() {
-            None => None,
-            Some(pos) => Some((pos, self.values.get(&pos)))
+        if let Some(val) = val_b {
+            self.values.insert(a, val);
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{Board};
-    use crate::position::Position;
-    use crate::dimension::Dimension;
+    /// Clear every field of this board whose position is empty in the given mask.
+    /// Both boards are aligned by absolute positions.
+    pub fn mask<M>(&mut self, mask: &Board<M>) {
+        self.values.retain(|pos, _| mask.values.contains_key(pos))
+    }
 
-    #[test]
-    fn get_field_works() {
-        let mut board = Board::new(Dimension::new(3, 3));
-        let pos = Position::new(1, 1);
-        assert_eq!(None, board.get_field(pos));
+    /// Set the dimension of this board to the given one. All values
+    /// which are not contained by the new dimension get dropped.
+    pub fn resize_to(&mut self, dimension: Dimension) {
+        self.dimension = dimension;
+        self.values.retain(|pos, _| dimension.contains_position(*pos))
+    }
 
-        board.values.insert(pos, 42);
-        assert_eq!(Some(&42), board.get_field(pos))
+    /// Grow the dimension of this board outwards by the given amount of fields on each
+    /// side. The new fields are empty and all values keep their positions.
+    pub fn pad(&mut self, left: usize, right: usize, top: usize, bottom: usize) {
+        self.dimension = Dimension {
+            origin: self.dimension.origin + (-(left as isize), -(top as isize)),
+            max: self.dimension.max + (right, bottom),
+        }
     }
 
-    /// A not resizeable board should allow to set fields inside its
-    /// dimension, but  do nothing if the provided #[Position]
-    /// is outside of it.
-    #[test]
-    fn set_field_not_resizeable_works() {
-        let dimension = Dimension::new(3, 3);
-        let mut board = Board::new(dimension);
-        let pos_in_dimension = Position::new(1, 1);
-        let pos_outside_dimension = Position::new(4, 4);
+    /// Crop this board to the smallest dimension containing all values matching the
+    /// given predicate. Values outside of it get dropped. If no value matches,
+    /// the board stays unchanged.
+    pub fn crop_to<F: Fn(&T) -> bool>(&mut self, keep: F) {
+        let positions = self.values.iter()
+            .filter(|(_, val)| keep(val))
+            .map(|(pos, _)| *pos)
+            .collect::<Vec<_>>();
 
-        assert!(dimension.contains_position(pos_in_dimension));
-        assert!(!dimension.contains_position(pos_outside_dimension));
+        if let Some(dimension) = Dimension::from_positions(&positions) {
+            self.resize_to(dimension)
+        }
+    }
 
-        board.set_field(pos_in_dimension, 42);
-        assert_eq!(Some(&42), board.values.get(&pos_in_dimension));
-        assert_eq!(board.dimension, dimension);
+    /// Shrink the dimension of this board to the smallest one which still
+    /// contains all occupied fields. An empty board keeps its dimension.
+    pub fn shrink_to_fit(&mut self) {
+        if let Some(dimension) = self.occupied_bounds() {
+            self.dimension = dimension
+        }
+    }
 
-        board.set_field(pos_outside_dimension, 42);
-        assert_eq!(None, board.values.get(&pos_outside_dimension));
-        assert_eq!(board.dimension, dimension);
+    /// Return the smallest dimension which contains all occupied fields,
+    /// or None if this board is empty.
+    pub fn occupied_bounds(&self) -> Option<Dimension> {
+        let positions = self.values.keys().copied().collect::<Vec<_>>();
+        Dimension::from_positions(&positions)
     }
 
-    /// A not resizeable board should allow to set fields regardless of its
-    /// dimension and resize if necessary.
-    #[test]
-    fn set_field_resizeable_works() {
-        let dimension = Dimension::new(3, 3);
-        let mut board = Board::new_resizeable(dimension);
-        let pos_in_dimension = Position::new(1, 1);
-        let pos_outside_dimension = Position::new(4, 4);
+    /// Check that every occupied field lies inside the dimension of this board.
+    /// Return a description of the first stray position otherwise.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.values.keys().filter(|pos| !self.dimension.contains_position(**pos)).min() {
+            None => Ok(()),
+            Some(pos) => Err(format!("Field at {} lies outside of {}", pos, self.dimension))
+        }
+    }
 
-        assert!(dimension.contains_position(pos_in_dimension));
-        assert!(!dimension.contains_position(pos_outside_dimension));
+    pub fn iter(&self) -> BoardIter<'_, T> {
+        BoardIter {
+            dimension_iter: self.dimension.iter(),
+            values: &self.values,
+        }
+    }
 
-        board.set_field(pos_in_dimension, 42);
-        assert_eq!(Some(&42), board.values.get(&pos_in_dimension));
-        assert_eq!(board.dimension, dimension);
+    /// Return an iterator over the fields from the given position in the given direction,
+    /// starting one step away from it and ending at the edge of this board.
+    /// A direction of (0, 0) yields no fields.
+    pub fn ray(&self, from: Position, direction: (isize, isize)) -> impl Iterator<Item = (Position, Option<&T>)> {
+        let dimension = self.dimension;
+        let moving = direction != (0, 0);
 
-        board.set_field(pos_outside_dimension, 42);
-        assert_eq!(Some(&42), board.values.get(&pos_outside_dimension));
-        assert_eq!(board.dimension, Dimension::new(5, 5));
+        successors(Some(from + direction), move |pos| Some(pos + direction))
+            .take_while(move |pos| moving && dimension.contains_position(*pos))
+            .map(move |pos| (pos, self.values.get(&pos)))
     }
 
-    /// If the field at the target position is not empty a set_field call should overwrite its value.
-    #[test]
-    fn set_field_existing_works() {
-        let mut board = Board::<usize>::new(Dimension::new(3, 3));
-        let pos = Position::new(0, 0);
+    /// Return an iterator over all diagonals of this board, each from its top end to its bottom end.
+    ///
+    /// Main diagonals run to the bottom right and start with the one in the top right corner.
+    /// Anti diagonals (if 'anti' is true) run to the bottom left and start with the one in the
+    /// top left corner. Both continue with the diagonals starting further along the top row
+    /// and then with those starting on the side column.
+    pub fn diagonals(&self, anti: bool) -> impl Iterator<Item = Vec<(Position, Option<&T>)>> {
+        let Dimension { origin, max } = self.dimension;
+        let (direction, top, side): (_, Vec<_>, _) = match anti {
+            false => ((1, 1), (origin.x..=max.x).rev().collect(), origin.x),
+            true => ((-1, 1), (origin.x..=max.x).collect(), max.x)
+        };
 
-        assert_eq!(None, board.get_field(pos));
+        let starts = top.into_iter().map(move |x| Position::new(x, origin.y))
+            .chain((origin.y + 1..=max.y).map(move |y| Position::new(side, y)));
 
-        board.set_field(pos, 42);
-        assert_eq!(Some(&42), board.get_field(pos));
+        starts.map(move |start| once((start, self.values.get(&start))).chain(self.ray(start, direction)).collect())
+    }
 
-        board.set_field(pos, 43);
-        assert_eq!(Some(&43), board.get_field(pos));
+    /// Return the first occupied field along the ray from the given position in the given
+    /// direction (see #[Board::ray]), or None if the ray leaves the board without a hit.
+    pub fn first_hit(&self, from: Position, direction: (isize, isize)) -> Option<(Position, &T)> {
+        self.ray(from, direction).find_map(|(pos, val)| val.map(|val| (pos, val)))
     }
 
-    /// A field should be cleared by Board::clear_field.
-    /// If the field was empty before, nothing should happen.
-    #[test]
-    fn clear_field_works() {
-        let mut board = Board::<usize>::new(Dimension::new(3, 3));
-        let pos = Position::new(0, 0);
-        assert_eq!(None, board.get_field(pos));
+    /// Return the length of the run of consecutive matching fields through the given
+    /// position, along the axis of the given direction (in both ways).
+    /// Return 0 if the field at the given position doesn't match.
+    ///
+    /// On a wrapping board, runs continue across the edges, but every field is counted at most once.
+    pub fn run_length<F: Fn(&T) -> bool>(&self, from: Position, direction: (isize, isize), matches: F) -> usize {
+        let from = self.normalize(from);
 
-        board.set_field(pos, 42);
-        assert_eq!(Some(&42), board.get_field(pos));
+        if !self.get_field(from).is_some_and(&matches) {
+            return 0;
+        }
 
-        assert_eq!(Some(42), board.clear_field(pos))
+        let count_matching = |direction: (isize, isize)| successors(Some(from), |pos| Some(self.normalize(*pos + direction)))
+            .skip(1)
+            .take_while(|pos| *pos != from && self.dimension.contains_position(*pos))
+            .take_while(|pos| self.values.get(pos).is_some_and(&matches))
+            .count();
+
+        let forward = count_matching(direction);
+        let steps = forward as isize + 1;
+
+        // The run went all the way around and back to the start.
+        if self.normalize(from + (direction.0 * steps, direction.1 * steps)) == from {
+            return steps as usize;
+        }
+
+        1 + forward + count_matching((-direction.0, -direction.1))
+    }
+
+    /// Return an iterator over all occupied fields of this board with a manhattan
+    /// distance of at most 'radius' to the given center.
+    pub fn cells_within(&self, center: Position, radius: usize) -> impl Iterator<Item = (Position, &T)> {
+        let Dimension { origin, max } = self.dimension;
+        let top = center.y.saturating_sub_unsigned(radius).max(origin.y);
+        let bottom = center.y.saturating_add_unsigned(radius).min(max.y);
+
+        (top..=bottom)
+            .flat_map(move |y| {
+                let width = radius - y.abs_diff(center.y);
+                let left = center.x.saturating_sub_unsigned(width).max(origin.x);
+                let right = center.x.saturating_add_unsigned(width).min(max.x);
+                (left..=right).map(move |x| Position::new(x, y))
+            })
+            .filter_map(move |pos| self.values.get(&pos).map(|val| (pos, val)))
+    }
+
+    /// Return an iterator over all fields of this board with a chebyshev distance of exactly
+    /// 'radius' to the given center, which form a square ring. The fields are ordered clockwise,
+    /// starting at the top left corner. A radius of 0 only yields the center.
+    pub fn iter_ring(&self, center: Position, radius: usize) -> impl Iterator<Item = (Position, Option<&T>)> {
+        let Dimension { origin, max } = self.dimension;
+        let Position { x, y } = center;
+        let inner = radius.saturating_sub(1);
+
+        // Every side is only walked if its line is on this board, and only along the part which is.
+        let line = |fixed: Option<isize>, min: isize, max: isize| fixed.filter(|fixed| radius > 0 && (min..=max).contains(fixed));
+        let span = |from: isize, to: isize, min: isize, max: isize| from.max(min)..=to.min(max);
+
+        let top = line(y.checked_sub_unsigned(radius), origin.y, max.y);
+        let top_span = span(x.saturating_sub_unsigned(radius), x.saturating_add_unsigned(inner), origin.x, max.x);
+        let right = line(x.checked_add_unsigned(radius), origin.x, max.x);
+        let right_span = span(y.saturating_sub_unsigned(radius), y.saturating_add_unsigned(inner), origin.y, max.y);
+        let bottom = line(y.checked_add_unsigned(radius), origin.y, max.y);
+        let bottom_span = span(x.saturating_sub_unsigned(inner), x.saturating_add_unsigned(radius), origin.x, max.x);
+        let left = line(x.checked_sub_unsigned(radius), origin.x, max.x);
+        let left_span = span(y.saturating_sub_unsigned(inner), y.saturating_add_unsigned(radius), origin.y, max.y);
+
+        once(center).filter(move |pos| radius == 0 && self.dimension.contains_position(*pos))
+            .chain(top.into_iter().flat_map(move |y| top_span.clone().map(move |x| Position::new(x, y))))
+            .chain(right.into_iter().flat_map(move |x| right_span.clone().map(move |y| Position::new(x, y))))
+            .chain(bottom.into_iter().flat_map(move |y| bottom_span.clone().rev().map(move |x| Position::new(x, y))))
+            .chain(left.into_iter().flat_map(move |x| left_span.clone().rev().map(move |y| Position::new(x, y))))
+            .map(move |pos| (pos, self.values.get(&pos)))
+    }
+
+    /// Return an iterator over all fields of this board, spiraling outwards from the given center.
+    /// The fields are ordered ring by ring, like in #[Board::iter_ring].
+    pub fn iter_spiral(&self, center: Position) -> impl Iterator<Item = (Position, Option<&T>)> {
+        let Dimension { origin, max } = self.dimension;
+        let radius = [center.x - origin.x, max.x - center.x, center.y - origin.y, max.y - center.y]
+            .iter()
+            .map(|d| d.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+
+        (0..=radius).flat_map(move |r| self.iter_ring(center, r))
+    }
+
+    /// Return if no field on the line between the given positions blocks the view.
+    /// The start and end of the line themselves are never checked.
+    pub fn has_line_of_sight<F: Fn(Option<&T>) -> bool>(&self, from: Position, to: Position, blocks: F) -> bool {
+        from.line_to(to)
+            .filter(|pos| *pos != from && *pos != to)
+            .all(|pos| !blocks(self.get_field(pos)))
+    }
+
+    /// Count how many of the eight neighbors of the given position are occupied
+    /// by a value matching the given predicate. Neighbors outside of this board
+    /// never match, unless it wraps around its edges.
+    pub fn count_neighbors_where<F: Fn(&T) -> bool>(&self, position: Position, f: F) -> usize {
+        position.diagonal_neighbors()
+            .iter()
+            .filter_map(|pos| self.get_field(*pos))
+            .filter(|val| f(val))
+            .count()
+    }
+
+    /// Fold over the fields orthogonally next to the given position (or all eight
+    /// neighbors if 'diagonal' is true), as returned by #[Board::neighbor_positions].
+    pub fn fold_neighbors<B, F>(&self, position: Position, init: B, diagonal: bool, f: F) -> B where F: Fn(B, Position, Option<&T>) -> B {
+        self.neighbor_positions(position, diagonal)
+            .into_iter()
+            .fold(init, |acc, pos| f(acc, pos, self.values.get(&pos)))
+    }
+
+    /// Return the positions orthogonally next to the given one (or all eight neighbors
+    /// if 'diagonal' is true) which are on this board.
+    ///
+    /// On a wrapping board, neighbors across an edge wrap around like in #[Board::get_field],
+    /// so on very small boards the same position can occur more than once.
+    pub fn neighbor_positions(&self, position: Position, diagonal: bool) -> Vec<Position> {
+        let neighbors = match diagonal {
+            true => position.diagonal_neighbors().to_vec(),
+            false => position.orthogonal_neighbors().to_vec()
+        };
+
+        neighbors.into_iter()
+            .map(|pos| self.normalize(pos))
+            .filter(|pos| self.dimension.contains_position(*pos))
+            .collect()
+    }
+
+    /// Return mutable references to the values of all occupied fields orthogonally next
+    /// to the given one, in the order of #[Position::orthogonal_neighbors].
+    ///
+    /// The neighbors are the ones of #[Board::neighbor_positions]. If a position occurs more
+    /// than once there (on very small wrapping boards), only its first occurrence is returned.
+    pub fn neighbors_mut(&mut self, position: Position) -> Vec<(Position, &mut T)> {
+        let mut neighbors = vec![];
+        self.neighbor_positions(position, false)
+            .into_iter()
+            .for_each(|pos| if !neighbors.contains(&pos) { neighbors.push(pos) });
+
+        let values = match neighbors.len() {
+            1 => Self::get_distinct_mut::<1>(&mut self.values, &neighbors),
+            2 => Self::get_distinct_mut::<2>(&mut self.values, &neighbors),
+            3 => Self::get_distinct_mut::<3>(&mut self.values, &neighbors),
+            4 => Self::get_distinct_mut::<4>(&mut self.values, &neighbors),
+            _ => vec![]
+        };
+
+        neighbors.into_iter()
+            .zip(values)
+            .filter_map(|(pos, val_opt)| val_opt.map(|val| (pos, val)))
+            .collect()
+    }
+
+    /// Return a bitmask of the sides of the given field which are boundaries, which means the
+    /// predicate returns true for the value of the field and the value of the neighbor on that
+    /// side. The bits are #[SIDE_NORTH], #[SIDE_EAST], #[SIDE_SOUTH] and #[SIDE_WEST].
+    pub fn boundary_sides<F: Fn(Option<&T>, Option<&T>) -> bool>(&self, position: Position, is_boundary: F) -> u8 {
+        let value = self.get_field(position);
+
+        position.orthogonal_neighbors()
+            .iter()
+            .zip([SIDE_NORTH, SIDE_EAST, SIDE_SOUTH, SIDE_WEST].iter())
+            .filter(|(pos, _)| is_boundary(value, self.get_field(**pos)))
+            .fold(0, |mask, (_, side)| mask | side)
+    }
+
+    /// Advance this board by one generation, like a cellular automaton.
+    ///
+    /// The rule gets called for every position of this board with the current value
+    /// and the whole current board. Its result becomes the new value of the field.
+    /// All rules see the state from before the step, so updates don't affect each other.
+    pub fn step<F>(&mut self, rule: F) where F: Fn(Position, Option<&T>, &Board<T>) -> Option<T>, T: Clone {
+        let values = self.dimension.iter()
+            .filter_map(|pos| rule(pos, self.values.get(&pos), self).map(|val| (pos, val)))
+            .collect();
+
+        self.values = values
+    }
+
+    /// Apply a convolution kernel to this board and return the result for every field.
+    ///
+    /// Every entry of the kernel is an offset to a neighbor and the weight of its value.
+    /// Empty neighbors and neighbors outside of this board contribute the default value.
+    pub fn convolve(&self, kernel: &[(isize, isize, f64)], default: f64) -> Board<f64> where T: Into<f64> + Copy {
+        let values = self.dimension.iter()
+            .map(|pos| {
+                let sum = kernel.iter()
+                    .map(|(dx, dy, weight)| weight * self.get_field(pos + (*dx, *dy)).map_or(default, |val| (*val).into()))
+                    .sum();
+                (pos, sum)
+            })
+            .collect();
+
+        self.derive(self.dimension, values)
+    }
+
+    /// Return an iterator over all positions of this board, in the same order as #[Board::iter].
+    pub fn positions(&self) -> DimensionIterator {
+        self.dimension.iter()
+    }
+
+    /// Return an iterator over all fields of this board in row-major order, together with
+    /// their flat index (y - origin.y) * width + (x - origin.x).
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, Position, Option<&T>)> {
+        let origin = self.dimension.origin;
+        let width = self.dimension.width();
+
+        (0..self.dimension.field_amount()).map(move |index| {
+            let pos = origin + (index % width, index / width);
+            (index, pos, self.values.get(&pos))
+        })
+    }
+
+    /// Return an iterator over the fields of this board with an even (or odd) sum of
+    /// coordinates, like the black (or white) fields of a checkerboard.
+    pub fn iter_parity(&self, even: bool) -> impl Iterator<Item = (Position, Option<&T>)> {
+        let parity = match even {
+            true => 0,
+            false => 1
+        };
+
+        self.iter().filter(move |(pos, _)| (pos.x + pos.y).rem_euclid(2) == parity)
+    }
+
+    /// Return an iterator over all windows of w * h fields which fit completely on this board.
+    /// Every window is identified by its top left position and contains its fields in
+    /// row-major order. The windows themselves are in row-major order as well.
+    pub fn windows(&self, w: usize, h: usize) -> impl Iterator<Item = (Position, Vec<Option<&T>>)> {
+        let Dimension { origin, max } = self.dimension;
+        let (w, h) = (w as isize, h as isize);
+        let fits = w > 0 && h > 0;
+
+        (origin.y..=(max.y - h + 1))
+            .flat_map(move |y| (origin.x..=(max.x - w + 1)).map(move |x| Position::new(x, y)))
+            .filter(move |_| fits)
+            .map(move |top_left| {
+                let window = (0..h)
+                    .flat_map(|dy| (0..w).map(move |dx| top_left + (dx, dy)))
+                    .map(|pos| self.values.get(&pos))
+                    .collect();
+
+                (top_left, window)
+            })
+    }
+
+    /// Return an iterator over the rows of this board, from top to bottom. Every row
+    /// contains its fields from left to right, with mutable references to their values.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = Vec<(Position, Option<&mut T>)>> {
+        let Dimension { origin, max } = self.dimension;
+        let mut values = self.values.iter_mut()
+            .map(|(pos, val)| (*pos, val))
+            .collect::<HashMap<_, _>>();
+
+        (origin.y..=max.y)
+            .map(|y| (origin.x..=max.x)
+                .map(|x| (Position::new(x, y), values.remove(&Position::new(x, y))))
+                .collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Return the fields of this board as nested rows, where `grid[y][x]`
+    /// is the field (origin.x + x, origin.y + y).
+    pub fn as_grid(&self) -> Vec<Vec<Option<T>>> where T: Clone {
+        let origin = self.dimension.origin;
+
+        (0..self.dimension.height())
+            .map(|y| (0..self.dimension.width())
+                .map(|x| self.values.get(&(origin + (x, y))).cloned())
+                .collect())
+            .collect()
+    }
+
+    /// Return the fields of this board as comma separated lines, one line per row from top to bottom.
+    /// Every line ends with "\n". Empty fields are written as the given string and the origin of
+    /// this board is not written.
+    pub fn to_csv(&self, empty: &str) -> String where T: Display {
+        let Dimension { origin, max } = self.dimension;
+
+        (origin.y..=max.y)
+            .map(|y| (origin.x..=max.x)
+                .map(|x| self.values.get(&Position::new(x, y)).map_or(empty.to_string(), |val| val.to_string()))
+                .collect::<Vec<_>>()
+                .join(",") + "\n")
+            .collect()
+    }
+
+    /// Apply the given closure to the value of every occupied field.
+    pub fn map_in_place<F: FnMut(Position, &mut T)>(&mut self, mut f: F) {
+        self.values.iter_mut().for_each(|(pos, val)| f(*pos, val))
+    }
+
+    /// Return if the left and right half of this board mirror each other,
+    /// which means every row reads the same in both directions.
+    pub fn is_horizontally_symmetric(&self) -> bool where T: PartialEq {
+        let Dimension { origin, max } = self.dimension;
+
+        self.dimension.iter().all(|pos| self.values.get(&pos) == self.values.get(&Position::new(origin.x + max.x - pos.x, pos.y)))
+    }
+
+    /// Return if the upper and lower half of this board mirror each other,
+    /// which means every column reads the same in both directions.
+    pub fn is_vertically_symmetric(&self) -> bool where T: PartialEq {
+        let Dimension { origin, max } = self.dimension;
+
+        self.dimension.iter().all(|pos| self.values.get(&pos) == self.values.get(&Position::new(pos.x, origin.y + max.y - pos.y)))
+    }
+
+    /// Copy the fields of the given region, reflected across the given center axis of this board,
+    /// onto the mirrored side. Empty fields in the region clear their mirrored counterpart.
+    pub fn mirror_region(&mut self, source: Dimension, axis: Axis) where T: Clone {
+        let Dimension { origin, max } = self.dimension;
+        let mirror = |pos: Position| match axis {
+            Axis::Vertical => Position::new(origin.x + max.x - pos.x, pos.y),
+            Axis::Horizontal => Position::new(pos.x, origin.y + max.y - pos.y)
+        };
+
+        let changes = self.dimension.iter_region(source)
+            .map(|pos| (mirror(pos), self.values.get(&pos).cloned()))
+            .collect::<Vec<_>>();
+
+        self.apply_changes(changes)
+    }
+
+    /// Return every field whose value differs between this and the other board, together
+    /// with its value on this board and on the other one. The fields are ordered by position.
+    pub fn diff<'a>(&'a self, other: &'a Board<T>) -> Vec<(Position, Option<&'a T>, Option<&'a T>)> where T: PartialEq {
+        let mut positions = self.values.keys()
+            .chain(other.values.keys().filter(|pos| !self.values.contains_key(pos)))
+            .copied()
+            .collect::<Vec<_>>();
+        positions.sort();
+
+        positions.into_iter()
+            .map(|pos| (pos, self.values.get(&pos), other.values.get(&pos)))
+            .filter(|(_, old, new)| old != new)
+            .collect()
+    }
+
+    /// Return the largest rectangular region of this board which contains only empty
+    /// fields, or None if the board is full.
+    ///
+    /// Every row is treated as the base of a histogram of empty fields above it, whose
+    /// largest rectangle is found with a stack.
+    pub fn largest_empty_rect(&self) -> Option<Dimension> {
+        let origin = self.dimension.origin;
+        let width = self.dimension.width();
+        let mut heights = vec![0; width];
+        let mut largest: Option<(usize, Dimension)> = None;
+
+        for y in 0..self.dimension.height() {
+            for (x, height) in heights.iter_mut().enumerate() {
+                *height = match self.values.contains_key(&(origin + (x, y))) {
+                    true => 0,
+                    false => *height + 1
+                }
+            }
+
+            let mut stack: Vec<usize> = vec![];
+            for x in 0..=width {
+                let height = heights.get(x).copied().unwrap_or(0);
+
+                while let Some(top) = stack.last().copied().filter(|top| heights[*top] > height) {
+                    stack.pop();
+                    let left = stack.last().map_or(0, |l| l + 1);
+                    let area = heights[top] * (x - left);
+
+                    if largest.is_none_or(|(largest_area, _)| area > largest_area) {
+                        let rect_origin = origin + (left, y + 1 - heights[top]);
+                        largest = Some((area, Dimension::from_origin(rect_origin, x - left, heights[top])));
+                    }
+                }
+
+                stack.push(x);
+            }
+        }
+
+        largest.map(|(_, dimension)| dimension)
+    }
+
+    /// Replace every value equal to 'old' with a clone of 'new'.
+    /// Return how many values were replaced.
+    pub fn replace_value(&mut self, old: &T, new: T) -> usize where T: PartialEq + Clone {
+        let mut replaced = 0;
+
+        self.values.values_mut()
+            .filter(|val| *val == old)
+            .for_each(|val| {
+                *val = new.clone();
+                replaced += 1
+            });
+
+        replaced
+    }
+
+    /// Count how often every distinct value occurs on this board.
+    pub fn count_values(&self) -> HashMap<&T, usize> where T: Eq + Hash {
+        let mut counts = HashMap::new();
+        self.values.values().for_each(|val| *counts.entry(val).or_insert(0) += 1);
+        counts
+    }
+
+    /// Return all occupied fields sorted ascending by their value.
+    /// Fields with equal values are sorted by their position.
+    pub fn iter_by_value(&self) -> Vec<(Position, &T)> where T: Ord {
+        let mut fields = self.values.iter().map(|(pos, val)| (*pos, val)).collect::<Vec<_>>();
+        fields.sort_by(|(pos_a, val_a), (pos_b, val_b)| val_a.cmp(val_b).then(pos_a.cmp(pos_b)));
+        fields
+    }
+
+    /// Return a hash of the dimension and the occupied fields of this board, which is cheaper
+    /// to compute than the #[Hash] implementation as the fields don't need to be sorted.
+    pub fn checksum(&self) -> u64 where T: Hash {
+        let fields = self.values.iter()
+            .map(|field| {
+                let mut hasher = DefaultHasher::new();
+                field.hash(&mut hasher);
+                hasher.finish()
+            })
+            .fold(0, |acc, hash| acc ^ hash);
+
+        let mut hasher = DefaultHasher::new();
+        self.dimension.hash(&mut hasher);
+        fields.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Return a board which is 'sx' times wider and 'sy' times higher than this one, where every
+    /// field becomes a block of sx * sy fields with its value. Panics if a factor is zero.
+    pub fn upscale(&self, sx: usize, sy: usize) -> Board<T> where T: Clone {
+        let origin = self.dimension.origin;
+        let block = Dimension::new(sx, sy);
+
+        let values = self.values.iter()
+            .flat_map(|(pos, val)| {
+                let block_origin = origin + Position::new((pos.x - origin.x) * sx as isize, (pos.y - origin.y) * sy as isize);
+                block.iter().map(move |offset| (block_origin + offset, val.clone()))
+            })
+            .collect();
+
+        self.derive(self.dimension.scale(sx, sy), values)
+    }
+
+    /// Return a board which is 'sx' times narrower and 'sy' times lower than this one, where every
+    /// block of sx * sy fields becomes a single field. The occupied values of a block get passed
+    /// to 'reduce' (in the order of #[Dimension::iter]), which computes the value of the new field.
+    ///
+    /// Blocks at the right and bottom edge may be smaller if the size of this board is not
+    /// a multiple of the factors. Panics if a factor is zero.
+    pub fn downsample<F: Fn(&[&T]) -> Option<T>>(&self, sx: usize, sy: usize, reduce: F) -> Board<T> {
+        let origin = self.dimension.origin;
+        let dimension = Dimension::from_origin(
+            origin,
+            self.dimension.width().div_ceil(sx),
+            self.dimension.height().div_ceil(sy),
+        );
+
+        let values = dimension.iter()
+            .filter_map(|pos| {
+                let block_origin = origin + Position::new((pos.x - origin.x) * sx as isize, (pos.y - origin.y) * sy as isize);
+                let block_values = self.dimension.iter_region(Dimension::from_origin(block_origin, sx, sy))
+                    .filter_map(|block_pos| self.values.get(&block_pos))
+                    .collect::<Vec<_>>();
+
+                reduce(&block_values).map(|val| (pos, val))
+            })
+            .collect();
+
+        self.derive(dimension, values)
+    }
+
+    /// Return this board rotated by 90 degrees clockwise (with the y-axis pointing downwards).
+    /// The origin stays the same, while width and height get swapped.
+    pub fn rotate_90(&self) -> Board<T> where T: Clone {
+        let origin = self.dimension.origin;
+        let height = self.dimension.height() as isize;
+        let dimension = Dimension::from_origin(origin, self.dimension.height(), self.dimension.width());
+
+        let values = self.values.iter()
+            .map(|(pos, val)| {
+                let relative = pos - &origin;
+                (origin + Position::new(height - 1 - relative.y, relative.x), val.clone())
+            })
+            .collect();
+
+        self.derive(dimension, values)
+    }
+
+    /// Return this board rotated by 180 degrees. The dimension stays the same.
+    pub fn rotate_180(&self) -> Board<T> where T: Clone {
+        let Dimension { origin, max } = self.dimension;
+
+        let values = self.values.iter()
+            .map(|(pos, val)| (Position::new(max.x - (pos.x - origin.x), max.y - (pos.y - origin.y)), val.clone()))
+            .collect();
+
+        self.derive(self.dimension, values)
+    }
+
+    /// Combine this and the other board field by field. The closure gets called for every
+    /// position in the union of both dimensions with the values of both boards and returns
+    /// the value of the new board. The new board keeps the flags of this one.
+    pub fn merge_with<U, V, F>(mut self, mut other: Board<U>, f: F) -> Board<V> where F: Fn(Option<T>, Option<U>) -> Option<V> {
+        let corners = [self.dimension.origin, self.dimension.max, other.dimension.origin, other.dimension.max];
+        let dimension = Dimension::from_positions(&corners).expect("corners are never empty");
+        let mut values = std::mem::take(&mut self.values);
+
+        let merged = dimension.iter()
+            .filter_map(|pos| f(values.remove(&pos), other.values.remove(&pos)).map(|val| (pos, val)))
+            .collect();
+
+        self.derive(dimension, merged)
+    }
+
+    /// Consume this board and return an iterator over all of its values, in arbitrary order.
+    pub fn into_values(self) -> impl Iterator<Item = T> {
+        self.values.into_values()
+    }
+
+    /// Return a board with the same flags as this one, but the given dimension and values.
+    fn derive<U>(&self, dimension: Dimension, values: HashMap<Position, U>) -> Board<U> {
+        Board {
+            resizeable: self.resizeable,
+            wrapping: self.wrapping,
+            dimension,
+            values,
+        }
+    }
+
+    /// Return mutable references to the values of exactly N distinct positions.
+    fn get_distinct_mut<'a, const N: usize>(values: &'a mut HashMap<Position, T>, positions: &[Position]) -> Vec<Option<&'a mut T>> {
+        let positions: [Position; N] = positions.try_into().expect("the amount of positions must be N");
+        IntoIterator::into_iter(values.get_disjoint_mut(positions.each_ref())).collect()
+    }
+
+    /// Map the given position onto the dimension if this board wraps around its edges.
+    fn normalize(&self, position: Position) -> Position {
+        match self.wrapping {
+            true => self.dimension.wrap_position(position),
+            false => position
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl<T> Board<T> {
+    /// Create an image of this board, where every field is a pixel.
+    /// The closure determines the color of a field from its value.
+    pub fn to_image<F: Fn(Option<&T>) -> [u8; 3]>(&self, to_rgb: F) -> image::RgbImage {
+        let origin = self.dimension.origin;
+
+        image::RgbImage::from_fn(self.dimension.width() as u32, self.dimension.height() as u32, |x, y| {
+            image::Rgb(to_rgb(self.values.get(&(origin + (x as usize, y as usize)))))
+        })
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T> Board<T> where T: Sync {
+    /// Return a parallel iterator over all occupied fields, in arbitrary order.
+    pub fn par_filled(&self) -> impl rayon::iter::ParallelIterator<Item = (Position, &T)> {
+        use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+        self.values.par_iter().map(|(pos, val)| (*pos, val))
+    }
+}
+
+impl<T> PartialEq for Board<T> where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.resizeable == other.resizeable
+            && self.wrapping == other.wrapping
+            && self.dimension == other.dimension
+            && self.values == other.values
+    }
+}
+
+impl<T> Eq for Board<T> where T: Eq {}
+
+impl<T> Hash for Board<T> where T: Hash {
+    /// Hash the occupied fields ordered by position, as the order of the underlying map is not stable.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.resizeable.hash(state);
+        self.wrapping.hash(state);
+        self.dimension.hash(state);
+
+        let mut fields = self.values.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|(pos, _)| **pos);
+        fields.hash(state);
+    }
+}
+
+impl<T> IntoIterator for Board<T> {
+    type Item = (Position, T);
+    type IntoIter = IntoIter<Position, T>;
+
+    /// Consume this board and return an iterator over all occupied fields, in arbitrary order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<T> Extend<(Position, T)> for Board<T> {
+    /// Set all given fields, following the same rules as #[Board::set_field].
+    fn extend<I: IntoIterator<Item = (Position, T)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(pos, val)| self.set_field(pos, val))
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for Board<T> {
+    /// Create a resizeable board with the smallest dimension containing all given fields.
+    /// If no fields are given, the board has the dimension 1x1 at (0, 0).
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let fields = iter.into_iter().collect::<Vec<_>>();
+        let positions = fields.iter().map(|(pos, _)| *pos).collect::<Vec<_>>();
+        let dimension = Dimension::from_positions(&positions).unwrap_or_else(|| Dimension::new(1, 1));
+
+        let mut board = Board::new_resizeable(dimension);
+        board.extend(fields);
+        board
+    }
+}
+
+impl Board<char> {
+    /// Parse a board from a multi-line string, where every character is a field.
+    /// The x-coordinate of a field is its column and the y-coordinate its row.
+    ///
+    /// Characters equal to 'empty' and the missing ends of lines shorter than
+    /// the widest one become empty fields. The board is at least 1x1 in size.
+    pub fn from_char_grid(s: &str, empty: char) -> Board<char> {
+        let width = s.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let height = s.lines().count();
+        let mut board = Board::new(Dimension::new(max(width, 1), max(height, 1)));
+
+        for (y, line) in s.lines().enumerate() {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c != empty)
+                .for_each(|(x, c)| board.set_field(Position::new_u(x, y), c))
+        }
+
+        board
+    }
+}
+
+impl<T> Board<T> where T: ToString {
+    /// Print a debug-representation of this board.
+    /// This method exists to provide a default print method
+    /// without overriding #[std::fmt::Display].
+    ///
+    /// The parameter 'empty' determines what should be displayed if
+    /// some board field is empty.
+    pub fn print(&self, empty: &str) {
+        let field_strings = self.iter()
+            .map(|(_, val_opt)| match val_opt {
+                None => empty.to_string(),
+                Some(val) => val.to_string()
+            })
+            .collect::<Vec<_>>();
+
+        let cell_size = Self::calculate_cell_size(empty, &self.dimension, &field_strings);
+
+        let width = self.dimension.width();
+        Self::print_width_indexes(width, cell_size.0);
+        for i in 0..self.dimension.height() {
+            Self::print_row(&i.to_string(), cell_size, &field_strings[(i * width)..(i * width + width)])
+        }
+        Self::print_width_indexes(width, cell_size.0);
+    }
+
+    fn calculate_cell_size(empty: &str, dimension: &Dimension, field_strings: &[String]) -> (usize, usize) {
+        let mut cell_size = (0, 0);
+        let mut update_size = |string: &str| cell_size = (max(cell_size.0, string.len()), max(cell_size.1, string.lines().count()));
+
+        update_size(empty);
+        update_size(&dimension.width().to_string());
+        update_size(&dimension.height().to_string());
+        field_strings.iter().for_each(|string| update_size(string));
+
+        cell_size
+    }
+
+    fn print_width_indexes(width: usize, cell_width: usize) {
+        let mut line = String::new();
+        line.push_str(Self::whitespace(cell_width + 1).as_str());
+
+        for i in 0..width {
+            let index_string = i.to_string();
+            line.push_str(&index_string);
+            line.push_str(&Self::whitespace(cell_width.saturating_sub(index_string.len()) + 1));
+        }
+
+        println!("{}", line);
+    }
+
+    fn print_row(index_string: &str, cell_size: (usize, usize), strings: &[String]) {
+        let mut row_lines = vec![vec![]; cell_size.1];
+        for string in strings {
+            let mut lines = string.lines();
+            (0..cell_size.1)
+                .for_each(|i| row_lines[i].push(lines.next().unwrap_or("")));
+        }
+
+        for (i, field_lines) in row_lines.iter().enumerate() {
+            let mut line_string = String::new();
+
+            for line in field_lines {
+                line_string.push_str(line);
+                line_string.push_str(&Self::whitespace(cell_size.0 + 1 - line.len()));
+            }
+
+            line_string = match i {
+                0 => format!(
+                    "{}{}{}{}",
+                    index_string,
+                    &Self::whitespace(cell_size.0 + 1 - index_string.len()),
+                    line_string,
+                    index_string
+                ),
+                _ => format!(
+                    "{}{}",
+                    &Self::whitespace(cell_size.0 + 1),
+                    line_string
+                )
+            };
+
+            println!("{}", line_string);
+        }
+    }
+
+    fn whitespace(length: usize) -> String {
+        (0..length).map(|_| String::from(" ")).collect()
+    }
+}
+
+/// Error which occurs when a field outside of a not resizeable board gets set.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct OutOfBounds(pub Position);
+
+impl Display for OutOfBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Position {} is out of the bounds of the board!", self.0)
+    }
+}
+
+impl Error for OutOfBounds {}
+
+/// Iterator over all board-positions with their current value.
+/// The item-type is (Position, Option<&'a T>). The positions
+/// are always in order.
+pub struct BoardIter<'a, T> {
+    dimension_iter: DimensionIterator,
+    values: &'a HashMap<Position, T>,
+}
+
+impl<'a, T> Iterator for BoardIter<'a, T> {
+    type Item = (Position, Option<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.dimension_iter.next() {
+            None => None,
+            Some(pos) => Some((pos, self.values.get(&pos)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    use crate::{Board, OutOfBounds, SIDE_EAST, SIDE_NORTH, SIDE_SOUTH, SIDE_WEST};
+    use crate::direction::{Axis, Direction};
+    use crate::position::Position;
+    use crate::dimension::Dimension;
+
+    #[test]
+    fn new_filled_works() {
+        let dimension = Dimension::from_origin(Position::new(-1, -1), 3, 4);
+        let board = Board::new_filled(dimension, 42);
+
+        assert!(!board.is_resizeable());
+        assert_eq!(dimension.field_amount(), board.count());
+        assert!(board.iter().all(|(_, val)| val == Some(&42)));
+    }
+
+    #[test]
+    fn from_fn_works() {
+        let board = Board::from_fn(Dimension::new(3, 3), |pos| match pos.x == pos.y {
+            true => Some(pos.x),
+            false => None
+        });
+
+        assert_eq!(vec![
+            vec![Some(0), None, None],
+            vec![None, Some(1), None],
+            vec![None, None, Some(2)],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn get_field_works() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        let pos = Position::new(1, 1);
+        assert_eq!(None, board.get_field(pos));
+
+        board.values.insert(pos, 42);
+        assert_eq!(Some(&42), board.get_field(pos))
+    }
+
+    #[test]
+    fn get_or_works() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        let pos = Position::new(1, 1);
+
+        assert_eq!(&0, board.get_or(pos, &0));
+
+        board.set_field(pos, 42);
+        assert_eq!(&42, board.get_or(pos, &0));
+    }
+
+    #[test]
+    fn get_field_or_default_works() {
+        let mut board = Board::<usize>::new(Dimension::new(3, 3));
+        let pos = Position::new(1, 1);
+
+        assert_eq!(Some(0), board.get_field_or_default(pos));
+        assert_eq!(None, board.get_field_or_default(Position::new(3, 3)));
+
+        board.set_field(pos, 42);
+        assert_eq!(Some(42), board.get_field_or_default(pos));
+    }
+
+    #[test]
+    fn get_disjoint_mut_works() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        let pos_a = Position::new(0, 0);
+        let pos_b = Position::new(2, 1);
+        board.set_field(pos_a, 42);
+        board.set_field(pos_b, 43);
+
+        let [a, b] = board.get_disjoint_mut([pos_a, pos_b]).unwrap();
+        std::mem::swap(a, b);
+
+        assert_eq!(Some(&43), board.get_field(pos_a));
+        assert_eq!(Some(&42), board.get_field(pos_b));
+    }
+
+    #[test]
+    fn get_disjoint_mut_overlapping_fails() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        let pos = Position::new(0, 0);
+        board.set_field(pos, 42);
+
+        assert!(board.get_disjoint_mut([pos, pos]).is_none());
+    }
+
+    #[test]
+    fn get_disjoint_mut_empty_fails() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        let pos = Position::new(0, 0);
+        board.set_field(pos, 42);
+
+        assert!(board.get_disjoint_mut([pos, Position::new(1, 1)]).is_none());
+    }
+
+    /// A not resizeable board should allow to set fields inside its
+    /// dimension, but  do nothing if the provided #[Position]
+    /// is outside of it.
+    #[test]
+    fn set_field_not_resizeable_works() {
+        let dimension = Dimension::new(3, 3);
+        let mut board = Board::new(dimension);
+        let pos_in_dimension = Position::new(1, 1);
+        let pos_outside_dimension = Position::new(4, 4);
+
+        assert!(dimension.contains_position(pos_in_dimension));
+        assert!(!dimension.contains_position(pos_outside_dimension));
+
+        board.set_field(pos_in_dimension, 42);
+        assert_eq!(Some(&42), board.values.get(&pos_in_dimension));
+        assert_eq!(board.dimension, dimension);
+
+        board.set_field(pos_outside_dimension, 42);
+        assert_eq!(None, board.values.get(&pos_outside_dimension));
+        assert_eq!(board.dimension, dimension);
+    }
+
+    /// A not resizeable board should allow to set fields regardless of its
+    /// dimension and resize if necessary.
+    #[test]
+    fn set_field_resizeable_works() {
+        let dimension = Dimension::new(3, 3);
+        let mut board = Board::new_resizeable(dimension);
+        let pos_in_dimension = Position::new(1, 1);
+        let pos_outside_dimension = Position::new(4, 4);
+
+        assert!(dimension.contains_position(pos_in_dimension));
+        assert!(!dimension.contains_position(pos_outside_dimension));
+
+        board.set_field(pos_in_dimension, 42);
+        assert_eq!(Some(&42), board.values.get(&pos_in_dimension));
+        assert_eq!(board.dimension, dimension);
+
+        board.set_field(pos_outside_dimension, 42);
+        assert_eq!(Some(&42), board.values.get(&pos_outside_dimension));
+        assert_eq!(board.dimension, Dimension::new(5, 5));
+    }
+
+    /// After locking a resizeable board, setting a field outside its dimension should do nothing.
+    #[test]
+    fn set_resizeable_works() {
+        let mut board = Board::<usize>::new_resizeable(Dimension::new(3, 3));
+        assert!(board.is_resizeable());
+
+        board.set_field(Position::new(3, 3), 42);
+        assert_eq!(Dimension::new(4, 4), board.dimension);
+
+        board.set_resizeable(false);
+        assert!(!board.is_resizeable());
+
+        let pos_outside_dimension = Position::new(5, 5);
+        board.set_field(pos_outside_dimension, 43);
+        assert_eq!(None, board.get_field(pos_outside_dimension));
+        assert_eq!(Dimension::new(4, 4), board.dimension);
+    }
+
+    #[test]
+    fn try_set_field_works() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        let pos = Position::new(1, 1);
+
+        assert_eq!(Ok(None), board.try_set_field(pos, 42));
+        assert_eq!(Ok(Some(42)), board.try_set_field(pos, 43));
+        assert_eq!(Some(&43), board.get_field(pos));
+    }
+
+    #[test]
+    fn try_set_field_out_of_bounds_fails() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        let pos = Position::new(3, 1);
+
+        assert_eq!(Err(OutOfBounds(pos)), board.try_set_field(pos, 42));
+        assert_eq!(None, board.get_field(pos));
+    }
+
+    #[test]
+    fn try_set_field_resizeable_works() {
+        let mut board = Board::new_resizeable(Dimension::new(3, 3));
+        let pos = Position::new(3, 1);
+
+        assert_eq!(Ok(None), board.try_set_field(pos, 42));
+        assert_eq!(Some(&42), board.get_field(pos));
+        assert_eq!(Dimension::new(4, 3), board.dimension);
+    }
+
+    /// If the field at the target position is not empty a set_field call should overwrite its value.
+    #[test]
+    fn set_field_existing_works() {
+        let mut board = Board::<usize>::new(Dimension::new(3, 3));
+        let pos = Position::new(0, 0);
+
+        assert_eq!(None, board.get_field(pos));
+
+        board.set_field(pos, 42);
+        assert_eq!(Some(&42), board.get_field(pos));
+
+        board.set_field(pos, 43);
+        assert_eq!(Some(&43), board.get_field(pos));
+    }
+
+    #[test]
+    fn draw_line_works() {
+        let mut board = Board::new(Dimension::new(4, 4));
+        board.draw_line(Position::new(0, 0), Position::new(5, 5), 42);
+
+        let mut positions = board.values.keys().copied().collect::<Vec<_>>();
+        positions.sort();
+        assert_eq!(vec![
+            Position::new(0, 0),
+            Position::new(1, 1),
+            Position::new(2, 2),
+            Position::new(3, 3),
+        ], positions);
+        assert!(board.values.values().all(|val| *val == 42));
+    }
+
+    #[test]
+    fn draw_rect_filled_works() {
+        let mut board = Board::new(Dimension::new(5, 5));
+        let region = Dimension::from_origin(Position::new(1, 1), 3, 2);
+        board.draw_rect(region, 42, true);
+
+        assert_eq!(6, board.values.len());
+        assert!(board.values.keys().all(|pos| region.contains_position(*pos)));
+    }
+
+    /// The interior of a hollow rectangle should stay empty. Fields outside
+    /// of a not resizeable board are ignored.
+    #[test]
+    fn draw_rect_hollow_works() {
+        let mut board = Board::new(Dimension::new(4, 4));
+        let region = Dimension::from_origin(Position::new(1, 1), 4, 4);
+        board.draw_rect(region, 42, false);
+
+        assert_eq!(5, board.values.len());
+        assert_eq!(None, board.get_field(Position::new(2, 2)));
+        assert_eq!(Some(&42), board.get_field(Position::new(1, 1)));
+        assert_eq!(Some(&42), board.get_field(Position::new(3, 1)));
+        assert_eq!(Some(&42), board.get_field(Position::new(1, 3)));
+    }
+
+    #[test]
+    fn apply_changes_works() {
+        let mut board = Board::from_char_grid("ab.\nc..", '.');
+        board.apply_changes(vec![
+            (Position::new(1, 0), Some('x')),
+            (Position::new(0, 1), None),
+            (Position::new(2, 1), Some('d')),
+            (Position::new(3, 1), Some('e')),
+        ]);
+
+        assert_eq!(vec![
+            vec![Some('a'), Some('x'), None],
+            vec![None, None, Some('d')],
+        ], board.as_grid());
+    }
+
+    /// A field should be cleared by Board::clear_field.
+    /// If the field was empty before, nothing should happen.
+    #[test]
+    fn clear_field_works() {
+        let mut board = Board::<usize>::new(Dimension::new(3, 3));
+        let pos = Position::new(0, 0);
+        assert_eq!(None, board.get_field(pos));
+
+        board.set_field(pos, 42);
+        assert_eq!(Some(&42), board.get_field(pos));
+
+        assert_eq!(Some(42), board.clear_field(pos))
+    }
+
+    #[test]
+    fn clear_works() {
+        let mut board = Board::<usize>::new(Dimension::new(3, 3));
+        let pos_a = Position::new(0, 0);
+        let pos_b = Position::new(1, 1);
+
+        board.set_field(pos_a, 42);
+        assert_eq!(Some(&42), board.get_field(pos_a));
+        board.set_field(pos_b, 43);
+        assert_eq!(Some(&43), board.get_field(pos_b));
+
+        board.clear();
+        assert_eq!(None, board.get_field(pos_a));
+        assert_eq!(None, board.get_field(pos_b));
+    }
+
+    /// Shifting right should drop the rightmost column.
+    #[test]
+    fn shift_right_works() {
+        let mut board = Board::from_char_grid("ab\ncd", '.');
+        board.shift((1, 0));
+
+        assert_eq!(vec![
+            vec![None, Some('a')],
+            vec![None, Some('c')],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn shift_up_works() {
+        let mut board = Board::from_char_grid("ab\ncd", '.');
+        board.shift((0, -1));
+
+        assert_eq!(vec![
+            vec![Some('c'), Some('d')],
+            vec![None, None],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn shift_off_board_works() {
+        let mut board = Board::from_char_grid("ab\ncd", '.');
+        board.shift((-2, 5));
+
+        assert!(board.is_empty());
+        assert_eq!(Dimension::new(2, 2), board.dimension);
+    }
+
+    /// Shifting a board by its whole width should restore the original arrangement.
+    #[test]
+    fn shift_wrapping_full_turn_works() {
+        let mut board = Board::from_char_grid("ab.\nc.d", '.');
+        let grid = board.as_grid();
+
+        board.shift_wrapping((3, 0));
+        assert_eq!(grid, board.as_grid());
+
+        board.shift_wrapping((-6, 2));
+        assert_eq!(grid, board.as_grid());
+    }
+
+    #[test]
+    fn shift_wrapping_works() {
+        let mut board = Board::from_char_grid("ab.\nc.d", '.');
+        board.shift_wrapping((1, 1));
+
+        assert_eq!(vec![
+            vec![Some('d'), Some('c'), None],
+            vec![None, Some('a'), Some('b')],
+        ], board.as_grid());
+    }
+
+    /// Values should pack at the bottom of their column, keeping their order.
+    #[test]
+    fn apply_gravity_down_works() {
+        let mut board = Board::from_char_grid("a.\n.b\nc.\n..", '.');
+        board.apply_gravity(Direction::Down);
+
+        assert_eq!(vec![
+            vec![None, None],
+            vec![None, None],
+            vec![Some('a'), None],
+            vec![Some('c'), Some('b')],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn apply_gravity_left_works() {
+        let mut board = Board::from_char_grid(".a.b\n...c", '.');
+        board.apply_gravity(Direction::Left);
+
+        assert_eq!(vec![
+            vec![Some('a'), Some('b'), None, None],
+            vec![Some('c'), None, None, None],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn tile_from_works() {
+        let pattern = Board::from_char_grid("ab\nc.", '.');
+        let mut board = Board::new_filled(Dimension::from_origin(Position::new(-1, -1), 4, 4), 'x');
+
+        board.tile_from(&pattern);
+
+        assert_eq!(vec![
+            vec![Some('a'), Some('b'), Some('a'), Some('b')],
+            vec![Some('c'), None, Some('c'), None],
+            vec![Some('a'), Some('b'), Some('a'), Some('b')],
+            vec![Some('c'), None, Some('c'), None],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn swap_rows_works() {
+        let mut board = Board::from_char_grid("ab.\n...\nc.d", '.');
+        board.swap_rows(0, 2);
+
+        assert_eq!(vec![
+            vec![Some('c'), None, Some('d')],
+            vec![None, None, None],
+            vec![Some('a'), Some('b'), None],
+        ], board.as_grid());
+
+        board.swap_rows(0, 3);
+        assert_eq!(Some(&'c'), board.get_field(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn swap_columns_works() {
+        let mut board = Board::from_char_grid("ab.\n...\nc.d", '.');
+        board.swap_columns(1, 2);
+
+        assert_eq!(vec![
+            vec![Some('a'), None, Some('b')],
+            vec![None, None, None],
+            vec![Some('c'), Some('d'), None],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn mask_works() {
+        let mut board = Board::new_filled(Dimension::new(3, 3), 42);
+        let mut mask = Board::new(Dimension::from_origin(Position::new(1, 1), 3, 3));
+        mask.set_field(Position::new(1, 1), ());
+        mask.set_field(Position::new(2, 2), ());
+        mask.set_field(Position::new(3, 3), ());
+
+        board.mask(&mask);
+
+        assert_eq!(2, board.count());
+        assert_eq!(Some(&42), board.get_field(Position::new(1, 1)));
+        assert_eq!(Some(&42), board.get_field(Position::new(2, 2)));
+    }
+
+    /// Shrinking a board should drop all values outside the new dimension.
+    #[test]
+    fn resize_to_shrink_works() {
+        let mut board = Board::<usize>::new(Dimension::new(3, 3));
+        let pos_inside = Position::new(1, 1);
+        let pos_outside = Position::new(2, 2);
+        board.set_field(pos_inside, 42);
+        board.set_field(pos_outside, 43);
+
+        let dimension = Dimension::new(2, 2);
+        board.resize_to(dimension);
+
+        assert_eq!(dimension, board.dimension);
+        assert_eq!(Some(&42), board.get_field(pos_inside));
+        assert_eq!(None, board.get_field(pos_outside));
+    }
+
+    /// Growing a board should keep all of its values.
+    #[test]
+    fn resize_to_grow_works() {
+        let mut board = Board::<usize>::new(Dimension::new(2, 2));
+        let pos_a = Position::new(0, 0);
+        let pos_b = Position::new(1, 1);
+        board.set_field(pos_a, 42);
+        board.set_field(pos_b, 43);
+
+        let dimension = Dimension::new(4, 4);
+        board.resize_to(dimension);
+
+        assert_eq!(dimension, board.dimension);
+        assert_eq!(Some(&42), board.get_field(pos_a));
+        assert_eq!(Some(&43), board.get_field(pos_b));
+    }
+
+    #[test]
+    fn pad_works() {
+        let mut board = Board::from_char_grid("a..\n.b.\n..c", '.');
+        board.pad(1, 1, 1, 1);
+
+        assert_eq!(Dimension::from_origin(Position::new(-1, -1), 5, 5), board.dimension);
+        assert_eq!(3, board.count());
+        assert_eq!(Some(&'a'), board.get_field(Position::new(0, 0)));
+        assert_eq!(Some(&'b'), board.get_field(Position::new(1, 1)));
+        assert_eq!(Some(&'c'), board.get_field(Position::new(2, 2)));
+    }
+
+    #[test]
+    fn crop_to_works() {
+        let mut board = Board::from_char_grid("....\n..ab\n.._c", '.');
+        board.crop_to(|val| *val != '_');
+
+        assert_eq!(Dimension::from_origin(Position::new(2, 1), 2, 2), board.dimension);
+        assert_eq!(vec![
+            vec![Some('a'), Some('b')],
+            vec![Some('_'), Some('c')],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn crop_to_nothing_works() {
+        let mut board = Board::from_char_grid("....\n..ab", '.');
+        board.crop_to(|_| false);
+
+        assert_eq!(Dimension::new(4, 2), board.dimension);
+        assert_eq!(2, board.count());
+    }
+
+    /// After shrinking, the dimension should tightly bound the remaining fields.
+    #[test]
+    fn shrink_to_fit_works() {
+        let mut board = Board::<usize>::new_resizeable(Dimension::new(10, 10));
+        let pos_a = Position::new(2, 3);
+        let pos_b = Position::new(8, 9);
+        board.set_field(pos_a, 42);
+        board.set_field(pos_b, 43);
+        board.clear_field(pos_b);
+
+        board.shrink_to_fit();
+
+        assert_eq!(Dimension::from_origin(pos_a, 1, 1), board.dimension);
+        assert_eq!(Some(&42), board.get_field(pos_a));
+    }
+
+    #[test]
+    fn occupied_bounds_works() {
+        let mut board = Board::new(Dimension::new(10, 10));
+        assert_eq!(None, board.occupied_bounds());
+
+        board.set_field(Position::new(1, 8), 42);
+        board.set_field(Position::new(7, 2), 43);
+
+        assert_eq!(Some(Dimension::from_origin(Position::new(1, 2), 7, 7)), board.occupied_bounds());
+        assert_eq!(Dimension::new(10, 10), board.dimension);
+    }
+
+    #[test]
+    fn shrink_to_fit_empty_works() {
+        let dimension = Dimension::new(10, 10);
+        let mut board = Board::<usize>::new_resizeable(dimension);
+
+        board.shrink_to_fit();
+
+        assert_eq!(dimension, board.dimension);
+    }
+
+    /// On a wrapping board, a position one past the right edge
+    /// should be the same field as the one on the left edge.
+    #[test]
+    fn wrapping_works() {
+        let mut board = Board::<usize>::new_wrapping(Dimension::new(3, 3));
+        let past_right_edge = Position::new(3, 1);
+        let left_edge = Position::new(0, 1);
+
+        board.set_field(past_right_edge, 42);
+        assert_eq!(Some(&42), board.get_field(left_edge));
+        assert_eq!(Some(&42), board.get_field(past_right_edge));
+        assert_eq!(None, board.values.get(&past_right_edge));
+
+        assert_eq!(Some(42), board.clear_field(Position::new(-3, 1)));
+        assert_eq!(None, board.get_field(left_edge));
+    }
+
+    #[test]
+    fn extend_works() {
+        let mut board = Board::new_resizeable(Dimension::new(3, 3));
+        board.extend(vec![
+            (Position::new(1, 1), 42),
+            (Position::new(-1, 4), 43),
+        ]);
+
+        assert_eq!(Dimension::from_origin(Position::new(-1, 0), 4, 5), board.dimension);
+        assert_eq!(Some(&42), board.get_field(Position::new(1, 1)));
+        assert_eq!(Some(&43), board.get_field(Position::new(-1, 4)));
+    }
+
+    #[test]
+    fn from_iter_works() {
+        let board: Board<usize> = vec![
+            (Position::new(1, 2), 42),
+            (Position::new(3, -1), 43),
+            (Position::new(2, 0), 44),
+        ].into_iter().collect();
+
+        assert!(board.resizeable);
+        assert_eq!(Dimension::from_origin(Position::new(1, -1), 3, 4), board.dimension);
+        assert_eq!(Some(&42), board.get_field(Position::new(1, 2)));
+        assert_eq!(Some(&43), board.get_field(Position::new(3, -1)));
+        assert_eq!(Some(&44), board.get_field(Position::new(2, 0)));
+    }
+
+    #[test]
+    fn from_iter_empty_works() {
+        let board: Board<usize> = Vec::new().into_iter().collect();
+
+        assert_eq!(Dimension::new(1, 1), board.dimension);
+        assert!(board.values.is_empty());
+    }
+
+    #[test]
+    fn eq_works() {
+        let board = Board::from_char_grid("ab\n.c", '.');
+
+        assert!(board == Board::from_char_grid("ab\n.c", '.'));
+        assert!(board != Board::from_char_grid("ab\nc.", '.'));
+        assert!(board != Board::from_char_grid("ab.\n.c.", '.'));
+    }
+
+    /// Equal boards should produce the same hash, regardless of the order of insertion.
+    #[test]
+    fn hash_works() {
+        let hash = |board: &Board<usize>| {
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            hasher.finish()
+        };
+        let positions = Dimension::new(10, 10).iter().collect::<Vec<_>>();
+
+        let mut board_a = Board::new(Dimension::new(10, 10));
+        positions.iter().for_each(|pos| board_a.set_field(*pos, pos.x as usize));
+        let mut board_b = Board::new(Dimension::new(10, 10));
+        positions.iter().rev().for_each(|pos| board_b.set_field(*pos, pos.x as usize));
+
+        assert!(board_a == board_b);
+        assert_eq!(hash(&board_a), hash(&board_b));
+
+        let mut set = HashSet::new();
+        set.insert(board_a);
+        assert!(set.contains(&board_b));
+    }
+
+    #[test]
+    fn upscale_works() {
+        let board = Board::from_char_grid("a.\n.b", '.');
+        let upscaled = board.upscale(2, 2);
+
+        assert_eq!(Dimension::new(4, 4), upscaled.dimension);
+        assert_eq!(vec![
+            vec![Some('a'), Some('a'), None, None],
+            vec![Some('a'), Some('a'), None, None],
+            vec![None, None, Some('b'), Some('b')],
+            vec![None, None, Some('b'), Some('b')],
+        ], upscaled.as_grid());
+    }
+
+    #[test]
+    fn upscale_custom_origin_works() {
+        let mut board = Board::new(Dimension::from_origin(Position::new(1, 1), 2, 1));
+        board.set_field(Position::new(2, 1), 42);
+        let upscaled = board.upscale(3, 2);
+
+        assert_eq!(Dimension::from_origin(Position::new(1, 1), 6, 2), upscaled.dimension);
+        assert_eq!(6, upscaled.count());
+        assert_eq!(None, upscaled.get_field(Position::new(3, 1)));
+        assert_eq!(Some(&42), upscaled.get_field(Position::new(4, 1)));
+        assert_eq!(Some(&42), upscaled.get_field(Position::new(6, 2)));
+    }
+
+    #[test]
+    fn downsample_works() {
+        let board = Board::from_char_grid("aab.\nab..\n....\nc..d", '.');
+        let majority = |values: &[&char]| {
+            let mut counts = HashMap::new();
+            values.iter().for_each(|val| *counts.entry(**val).or_insert(0) += 1);
+            counts.into_iter().max_by_key(|(val, count)| (*count, *val)).map(|(val, _)| val)
+        };
+
+        let downsampled = board.downsample(2, 2, majority);
+
+        assert_eq!(Dimension::new(2, 2), downsampled.dimension);
+        assert_eq!(vec![
+            vec![Some('a'), Some('b')],
+            vec![Some('c'), Some('d')],
+        ], downsampled.as_grid());
+    }
+
+    #[test]
+    fn downsample_uneven_works() {
+        let board = Board::from_char_grid("a..\n...\n..b", '.');
+        let first = |values: &[&char]| values.first().map(|val| **val);
+
+        let downsampled = board.downsample(2, 2, first);
+
+        assert_eq!(Dimension::new(2, 2), downsampled.dimension);
+        assert_eq!(vec![
+            vec![Some('a'), None],
+            vec![None, Some('b')],
+        ], downsampled.as_grid());
+    }
+
+    #[test]
+    fn rotate_90_works() {
+        let board = Board::from_char_grid("ab.\n..c", '.');
+        let rotated = board.rotate_90();
+
+        assert_eq!(vec![
+            vec![None, Some('a')],
+            vec![None, Some('b')],
+            vec![Some('c'), None],
+        ], rotated.as_grid());
+    }
+
+    #[test]
+    fn rotate_180_works() {
+        let mut board = Board::new(Dimension::from_origin(Position::new(-1, 2), 3, 2));
+        board.set_field(Position::new(-1, 2), 'a');
+        board.set_field(Position::new(0, 2), 'b');
+        board.set_field(Position::new(1, 3), 'c');
+        let rotated = board.rotate_180();
+
+        assert_eq!(vec![
+            vec![Some('c'), None, None],
+            vec![None, Some('b'), Some('a')],
+        ], rotated.as_grid());
+        assert!(board.rotate_90().rotate_90() == rotated);
+    }
+
+    /// The merged board should prefer the values of the first board and fall back to the second.
+    #[test]
+    fn merge_with_works() {
+        let first = Board::from_char_grid("a.\n.b", '.');
+        let mut second = Board::new(Dimension::from_origin(Position::new(1, 1), 2, 2));
+        second.set_field(Position::new(1, 1), 'x');
+        second.set_field(Position::new(2, 1), 'y');
+        second.set_field(Position::new(2, 2), 'z');
+
+        let merged = first.merge_with(second, |a, b| a.or(b));
+
+        assert_eq!(Dimension::new(3, 3), merged.dimension);
+        assert_eq!(vec![
+            vec![Some('a'), None, None],
+            vec![None, Some('b'), Some('y')],
+            vec![None, None, Some('z')],
+        ], merged.as_grid());
+    }
+
+    #[test]
+    fn into_iter_works() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        board.set_field(Position::new(0, 0), String::from("a"));
+        board.set_field(Position::new(2, 1), String::from("b"));
+
+        let mut fields = board.into_iter().collect::<Vec<_>>();
+        fields.sort();
+        assert_eq!(vec![
+            (Position::new(0, 0), String::from("a")),
+            (Position::new(2, 1), String::from("b")),
+        ], fields)
+    }
+
+    #[test]
+    fn into_values_works() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        board.set_field(Position::new(0, 0), String::from("a"));
+        board.set_field(Position::new(2, 1), String::from("b"));
+
+        let mut values = board.into_values().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(vec![String::from("a"), String::from("b")], values)
+    }
+
+    #[test]
+    fn positions_works() {
+        let mut board = Board::new(Dimension::from_origin(Position::new(1, -1), 3, 2));
+        board.set_field(Position::new(2, 0), 42);
+
+        assert_eq!(
+            board.iter().map(|(pos, _)| pos).collect::<Vec<_>>(),
+            board.positions().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn iter_indexed_works() {
+        let mut board = Board::new(Dimension::from_origin(Position::new(1, 1), 3, 2));
+        board.set_field(Position::new(2, 2), 42);
+
+        let fields = board.iter_indexed().collect::<Vec<_>>();
+
+        assert_eq!(6, fields.len());
+        assert_eq!((0, Position::new(1, 1), None), fields[0]);
+        assert_eq!((2, Position::new(3, 1), None), fields[2]);
+        assert_eq!((3, Position::new(1, 2), None), fields[3]);
+        assert_eq!((4, Position::new(2, 2), Some(&42)), fields[4]);
+        assert_eq!((5, Position::new(3, 2), None), fields[5]);
+    }
+
+    /// Both parities should partition the board without overlapping.
+    #[test]
+    fn iter_parity_works() {
+        let dimension = Dimension::from_origin(Position::new(-1, 0), 3, 3);
+        let board = Board::<usize>::new(dimension);
+
+        let even = board.iter_parity(true).map(|(pos, _)| pos).collect::<Vec<_>>();
+        let odd = board.iter_parity(false).map(|(pos, _)| pos).collect::<Vec<_>>();
+
+        assert_eq!(4, even.len());
+        assert_eq!(5, odd.len());
+        assert!(even.contains(&Position::new(-1, 1)));
+        assert!(odd.contains(&Position::new(-1, 0)));
+        assert!(even.iter().all(|pos| !odd.contains(pos)));
+        assert!(dimension.iter().all(|pos| even.contains(&pos) || odd.contains(&pos)));
+    }
+
+    #[test]
+    fn windows_works() {
+        let board = Board::from_char_grid("ab..\ncd..\n..e.\n....", '.');
+        let windows = board.windows(2, 2).collect::<Vec<_>>();
+
+        assert_eq!(9, windows.len());
+        assert_eq!((Position::new(0, 0), vec![Some(&'a'), Some(&'b'), Some(&'c'), Some(&'d')]), windows[0]);
+        assert_eq!((Position::new(1, 1), vec![Some(&'d'), None, None, Some(&'e')]), windows[4]);
+    }
+
+    #[test]
+    fn windows_too_large_works() {
+        let board = Board::<char>::new(Dimension::new(3, 3));
+
+        assert_eq!(0, board.windows(4, 1).count());
+        assert_eq!(0, board.windows(0, 1).count());
+        assert_eq!(1, board.windows(3, 3).count());
+    }
+
+    #[test]
+    fn rows_mut_works() {
+        let mut board = Board::from_rows(vec![
+            vec![Some(1), None, Some(2)],
+            vec![Some(3), Some(4), None],
+        ]);
+
+        assert_eq!(2, board.rows_mut().count());
+        board.rows_mut()
+            .nth(1)
+            .unwrap()
+            .into_iter()
+            .for_each(|(_, val_opt)| if let Some(val) = val_opt { *val *= 10 });
+
+        assert_eq!(vec![
+            (Position::new(0, 0), Some(&1)),
+            (Position::new(0, 1), Some(&30)),
+            (Position::new(1, 0), None),
+            (Position::new(1, 1), Some(&40)),
+            (Position::new(2, 0), Some(&2)),
+            (Position::new(2, 1), None),
+        ], board.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn from_rows_works() {
+        let board = Board::from_rows(vec![
+            vec![Some(1), None],
+            vec![None, None, Some(2)],
+        ]);
+
+        assert_eq!(Dimension::new(3, 2), board.dimension);
+        assert_eq!(Some(&1), board.get_field(Position::new(0, 0)));
+        assert_eq!(Some(&2), board.get_field(Position::new(2, 1)));
+        assert_eq!(2, board.values.len());
+    }
+
+    #[test]
+    fn as_grid_works() {
+        let rows = vec![
+            vec![Some(1), None, Some(2)],
+            vec![None, Some(3), None],
+        ];
+
+        assert_eq!(rows, Board::from_rows(rows.clone()).as_grid());
+    }
+
+    #[test]
+    fn as_grid_custom_origin_works() {
+        let mut board = Board::new(Dimension::from_origin(Position::new(-1, 2), 2, 2));
+        board.set_field(Position::new(0, 2), 42);
+
+        assert_eq!(vec![
+            vec![None, Some(42)],
+            vec![None, None],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn from_char_grid_works() {
+        let board = Board::from_char_grid("#.#\n..@.\n#", '.');
+
+        assert_eq!(Dimension::new(4, 3), board.dimension);
+        assert_eq!(Some(&'#'), board.get_field(Position::new(0, 0)));
+        assert_eq!(None, board.get_field(Position::new(1, 0)));
+        assert_eq!(Some(&'#'), board.get_field(Position::new(2, 0)));
+        assert_eq!(Some(&'@'), board.get_field(Position::new(2, 1)));
+        assert_eq!(Some(&'#'), board.get_field(Position::new(0, 2)));
+        assert_eq!(4, board.values.len());
+    }
+
+    /// Fields after the end of a short line and trailing empty characters should stay empty.
+    #[test]
+    fn from_char_grid_trailing_empty_works() {
+        let board = Board::from_char_grid("a..\nb", '.');
+
+        assert_eq!(Dimension::new(3, 2), board.dimension);
+        assert_eq!(None, board.get_field(Position::new(2, 0)));
+        assert_eq!(None, board.get_field(Position::new(1, 1)));
+        assert_eq!(None, board.get_field(Position::new(2, 1)));
+        assert_eq!(2, board.values.len());
+    }
+
+    #[test]
+    fn ray_horizontal_works() {
+        let mut board = Board::new(Dimension::new(4, 4));
+        board.set_field(Position::new(3, 1), 42);
+
+        assert_eq!(vec![
+            (Position::new(2, 1), None),
+            (Position::new(3, 1), Some(&42)),
+        ], board.ray(Position::new(1, 1), (1, 0)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn ray_diagonal_works() {
+        let board = Board::<usize>::new(Dimension::new(4, 4));
+
+        assert_eq!(vec![
+            Position::new(2, 1),
+            Position::new(3, 0),
+        ], board.ray(Position::new(1, 2), (1, -1)).map(|(pos, _)| pos).collect::<Vec<_>>());
+        assert_eq!(0, board.ray(Position::new(1, 2), (0, 0)).count());
     }
 
     #[test]
-    fn clear_works() {
-        let mut board = Board::<usize>::new(Dimension::new(3, 3));
-        let pos_a = Position::new(0, 0);
-        let pos_b = Position::new(1, 1);
+    fn first_hit_works() {
+        let mut board = Board::new(Dimension::new(5, 5));
+        board.set_field(Position::new(0, 0), 'R');
+        board.set_field(Position::new(0, 3), 'p');
+        board.set_field(Position::new(0, 4), 'q');
 
-        board.set_field(pos_a, 42);
-        assert_eq!(Some(&42), board.get_field(pos_a));
-        board.set_field(pos_b, 43);
-        assert_eq!(Some(&43), board.get_field(pos_b));
+        assert_eq!(Some((Position::new(0, 3), &'p')), board.first_hit(Position::new(0, 0), (0, 1)));
+    }
+
+    #[test]
+    fn first_hit_nothing_works() {
+        let mut board = Board::new(Dimension::new(5, 5));
+        board.set_field(Position::new(0, 0), 'R');
+        board.set_field(Position::new(0, 3), 'p');
+
+        assert_eq!(None, board.first_hit(Position::new(0, 0), (1, 0)));
+    }
+
+    #[test]
+    fn run_length_works() {
+        let board = Board::from_char_grid(".xxx.\n..o..", '.');
+        let is_x = |val: &char| *val == 'x';
+
+        assert_eq!(3, board.run_length(Position::new(2, 0), (1, 0), is_x));
+        assert_eq!(3, board.run_length(Position::new(1, 0), (-1, 0), is_x));
+        assert_eq!(1, board.run_length(Position::new(1, 0), (0, 1), is_x));
+        assert_eq!(0, board.run_length(Position::new(0, 0), (1, 0), is_x));
+    }
+
+    #[test]
+    fn run_length_broken_works() {
+        let board = Board::from_char_grid("xx.xx\nxxoxx", '.');
+        let is_x = |val: &char| *val == 'x';
+
+        assert_eq!(2, board.run_length(Position::new(0, 0), (1, 0), is_x));
+        assert_eq!(2, board.run_length(Position::new(4, 1), (1, 0), is_x));
+    }
+
+    #[test]
+    fn run_length_wrapping_works() {
+        let mut board = Board::new_wrapping(Dimension::new(3, 1));
+        board.set_field(Position::new(0, 0), 'x');
+        board.set_field(Position::new(1, 0), 'x');
+        let is_x = |val: &char| *val == 'x';
+
+        assert_eq!(2, board.run_length(Position::new(3, 0), (1, 0), is_x));
+        assert_eq!(board.run_length(Position::new(0, 0), (1, 0), is_x), board.run_length(Position::new(3, 0), (1, 0), is_x));
+    }
+
+    #[test]
+    fn run_length_across_edge_works() {
+        let mut board = Board::new_wrapping(Dimension::new(3, 1));
+        board.set_field(Position::new(0, 0), 'x');
+        board.set_field(Position::new(2, 0), 'x');
+        let is_x = |val: &char| *val == 'x';
+
+        assert_eq!(2, board.run_length(Position::new(0, 0), (1, 0), is_x));
+        assert_eq!(2, board.run_length(Position::new(2, 0), (-1, 0), is_x));
+
+        board.set_field(Position::new(1, 0), 'x');
+        assert_eq!(3, board.run_length(Position::new(0, 0), (1, 0), is_x));
+        assert_eq!(3, board.run_length(Position::new(1, 0), (-1, 0), is_x));
+    }
+
+    #[test]
+    fn cells_within_works() {
+        let board = Board::from_char_grid("a...b\n..c..\nd...e", '.');
+
+        let mut cells = board.cells_within(Position::new(1, 1), 2).collect::<Vec<_>>();
+        cells.sort();
+
+        assert_eq!(vec![
+            (Position::new(0, 0), &'a'),
+            (Position::new(0, 2), &'d'),
+            (Position::new(2, 1), &'c'),
+        ], cells);
+    }
+
+    /// A huge radius should only visit the fields of the board.
+    #[test]
+    fn cells_within_huge_radius_works() {
+        let board = Board::from_char_grid("a...b\n..c..\nd...e", '.');
+
+        assert_eq!(5, board.cells_within(Position::new(1, 1), usize::MAX).count());
+        assert_eq!(5, board.cells_within(Position::new(-100, 50), 1 << 40).count());
+        assert_eq!(1, board.cells_within(Position::new(-2, 0), 2).count());
+    }
+
+    #[test]
+    fn has_line_of_sight_works() {
+        let mut board = Board::new(Dimension::new(5, 5));
+        board.set_field(Position::new(0, 0), 'a');
+        board.set_field(Position::new(4, 0), 'b');
+        board.set_field(Position::new(2, 2), '#');
+
+        let wall = |val: Option<&char>| val == Some(&'#');
+        assert!(board.has_line_of_sight(Position::new(0, 0), Position::new(4, 0), wall));
+        assert!(!board.has_line_of_sight(Position::new(0, 0), Position::new(4, 4), wall));
+        assert!(board.has_line_of_sight(Position::new(2, 2), Position::new(4, 4), wall));
+    }
+
+    /// Adjacent fields have nothing in between, so they can always see each other.
+    #[test]
+    fn has_line_of_sight_adjacent_works() {
+        let board = Board::<char>::new(Dimension::new(3, 3));
+        assert!(board.has_line_of_sight(Position::new(1, 1), Position::new(2, 2), |_| true));
+        assert!(board.has_line_of_sight(Position::new(1, 1), Position::new(1, 0), |_| true));
+    }
+
+    #[test]
+    fn count_neighbors_where_works() {
+        // .#.
+        // ##.
+        // ..o
+        let mut board = Board::new(Dimension::new(3, 3));
+        board.set_field(Position::new(1, 0), true);
+        board.set_field(Position::new(0, 1), true);
+        board.set_field(Position::new(1, 1), true);
+        board.set_field(Position::new(2, 2), false);
+
+        let alive = |val: &bool| *val;
+        assert_eq!(3, board.count_neighbors_where(Position::new(0, 0), alive));
+        assert_eq!(3, board.count_neighbors_where(Position::new(1, 1), |_| true));
+        assert_eq!(2, board.count_neighbors_where(Position::new(1, 1), alive));
+        assert_eq!(1, board.count_neighbors_where(Position::new(2, 2), alive));
+        assert_eq!(1, board.count_neighbors_where(Position::new(1, 1), |val| !val));
+    }
+
+    #[test]
+    fn neighbor_positions_works() {
+        let board = Board::<usize>::new(Dimension::new(3, 3));
+
+        assert_eq!(4, board.neighbor_positions(Position::new(1, 1), false).len());
+        assert_eq!(8, board.neighbor_positions(Position::new(1, 1), true).len());
+
+        assert_eq!(vec![
+            Position::new(1, 1),
+            Position::new(2, 2),
+            Position::new(0, 2),
+        ], board.neighbor_positions(Position::new(1, 2), false));
+        assert_eq!(5, board.neighbor_positions(Position::new(1, 2), true).len());
+
+        assert_eq!(vec![
+            Position::new(1, 0),
+            Position::new(0, 1),
+        ], board.neighbor_positions(Position::new(0, 0), false));
+        assert_eq!(vec![
+            Position::new(1, 0),
+            Position::new(0, 1),
+            Position::new(1, 1),
+        ], board.neighbor_positions(Position::new(0, 0), true));
+    }
+
+    #[test]
+    fn fold_neighbors_works() {
+        let board = Board::from_rows(vec![
+            vec![Some(1), Some(2), Some(3)],
+            vec![Some(4), Some(5), None],
+            vec![Some(7), Some(8), Some(9)],
+        ]);
+        let sum = |acc: usize, _, val: Option<&usize>| acc + val.copied().unwrap_or(0);
+
+        assert_eq!(14, board.fold_neighbors(Position::new(1, 1), 0, false, sum));
+        assert_eq!(34, board.fold_neighbors(Position::new(1, 1), 0, true, sum));
+        assert_eq!(11, board.fold_neighbors(Position::new(0, 0), 0, true, sum));
+        assert_eq!(2, board.fold_neighbors(Position::new(0, 0), 0, false, |acc, _, _| acc + 1));
+    }
+
+    /// Folding and counting neighbors should agree on a wrapping board.
+    #[test]
+    fn fold_neighbors_wrapping_works() {
+        let mut board = Board::new_wrapping(Dimension::new(2, 2));
+        board.set_field(Position::new(1, 0), 1);
+        let count = |acc: usize, _, val: Option<&usize>| acc + val.map_or(0, |_| 1);
+
+        assert_eq!(2, board.count_neighbors_where(Position::new(0, 0), |_| true));
+        assert_eq!(2, board.fold_neighbors(Position::new(0, 0), 0, true, count));
+        assert_eq!(vec![
+            Position::new(0, 1),
+            Position::new(1, 0),
+            Position::new(0, 1),
+            Position::new(1, 0),
+        ], board.neighbor_positions(Position::new(0, 0), false));
+    }
+
+    #[test]
+    fn boundary_sides_works() {
+        let board = Board::from_char_grid("aba\nbab\naaa", '.');
+        let differs = |a: Option<&char>, b: Option<&char>| a != b;
+
+        assert_eq!(SIDE_NORTH | SIDE_EAST | SIDE_SOUTH | SIDE_WEST, board.boundary_sides(Position::new(0, 1), differs));
+        assert_eq!(SIDE_NORTH | SIDE_EAST | SIDE_WEST, board.boundary_sides(Position::new(1, 1), differs));
+        assert_eq!(SIDE_SOUTH, board.boundary_sides(Position::new(1, 2), differs));
+    }
+
+    #[test]
+    fn boundary_sides_uniform_works() {
+        let board = Board::new_filled(Dimension::new(3, 3), 'a');
+
+        assert_eq!(0, board.boundary_sides(Position::new(1, 1), |a, b| a != b));
+    }
+
+    /// A blinker should switch between horizontal and vertical orientation.
+    #[test]
+    fn step_blinker_works() {
+        let horizontal = [Position::new(1, 2), Position::new(2, 2), Position::new(3, 2)];
+        let vertical = [Position::new(2, 1), Position::new(2, 2), Position::new(2, 3)];
+        let mut board = Board::new(Dimension::new(5, 5));
+        horizontal.iter().for_each(|pos| board.set_field(*pos, ()));
+
+        let game_of_life = |pos: Position, val: Option<&()>, board: &Board<()>| {
+            match (val, board.count_neighbors_where(pos, |_| true)) {
+                (Some(_), 2) | (_, 3) => Some(()),
+                _ => None
+            }
+        };
+
+        board.step(game_of_life);
+        assert_eq!(3, board.values.len());
+        assert!(vertical.iter().all(|pos| board.get_field(*pos).is_some()));
+
+        board.step(game_of_life);
+        assert_eq!(3, board.values.len());
+        assert!(horizontal.iter().all(|pos| board.get_field(*pos).is_some()));
+    }
+
+    #[test]
+    fn is_full_works() {
+        let dimension = Dimension::new(2, 2);
+        let mut board = Board::new(dimension);
+        assert!(board.is_empty());
+        assert!(!board.is_full());
+
+        board.set_field(Position::new(0, 0), 42);
+        assert!(!board.is_empty());
+        assert!(!board.is_full());
+        assert_eq!(1, board.count());
+
+        dimension.iter().for_each(|pos| board.set_field(pos, 42));
+        assert!(board.is_full());
+        assert_eq!(4, board.count());
+    }
+
+    #[test]
+    fn count_in_region_works() {
+        let mut board = Board::new(Dimension::new(4, 4));
+        board.set_field(Position::new(0, 0), 42);
+        board.set_field(Position::new(1, 1), 42);
+        board.set_field(Position::new(3, 3), 42);
+
+        assert_eq!(2, board.count_in_region(Dimension::new(2, 2)));
+        assert_eq!(2, board.count_in_region(Dimension::from_origin(Position::new(1, 1), 5, 5)));
+        assert_eq!(0, board.count_in_region(Dimension::from_origin(Position::new(2, 0), 2, 2)));
+    }
+
+    /// Clearing a board should keep its capacity, reserving should increase it.
+    #[test]
+    fn capacity_works() {
+        let dimension = Dimension::new(10, 10);
+        let mut board = Board::<usize>::new(dimension);
+        dimension.iter().for_each(|pos| board.set_field(pos, 42));
+        let capacity = board.capacity();
+        assert!(capacity >= dimension.field_amount());
 
         board.clear();
-        assert_eq!(None, board.get_field(pos_a));
-        assert_eq!(None, board.get_field(pos_b));
+        assert_eq!(capacity, board.capacity());
+
+        board.reserve(capacity + 1);
+        assert!(board.capacity() > capacity);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn to_image_works() {
+        let mut board = Board::new(Dimension::from_origin(Position::new(1, 1), 3, 2));
+        board.set_field(Position::new(3, 2), 42);
+
+        let image = board.to_image(|val| match val {
+            None => [0, 0, 0],
+            Some(_) => [255, 0, 0]
+        });
+
+        assert_eq!((3, 2), image.dimensions());
+        assert_eq!(&image::Rgb([255, 0, 0]), image.get_pixel(2, 1));
+        assert_eq!(&image::Rgb([0, 0, 0]), image.get_pixel(0, 0));
+    }
+
+    #[test]
+    fn map_in_place_works() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        board.set_field(Position::new(0, 0), 21);
+        board.set_field(Position::new(2, 1), 5);
+
+        board.map_in_place(|_, val| *val *= 2);
+
+        assert_eq!(Some(&42), board.get_field(Position::new(0, 0)));
+        assert_eq!(Some(&10), board.get_field(Position::new(2, 1)));
+        assert_eq!(None, board.get_field(Position::new(1, 1)));
+        assert_eq!(2, board.count());
+    }
+
+    #[test]
+    fn is_horizontally_symmetric_works() {
+        assert!(Board::from_char_grid("a.a\nbcb", '.').is_horizontally_symmetric());
+        assert!(!Board::from_char_grid("a..\nbcb", '.').is_horizontally_symmetric());
+        assert!(!Board::from_char_grid("a.a\nbcd", '.').is_horizontally_symmetric());
+    }
+
+    #[test]
+    fn is_vertically_symmetric_works() {
+        assert!(Board::from_char_grid("ab\n.c\nab", '.').is_vertically_symmetric());
+        assert!(!Board::from_char_grid("ab\n.c\na.", '.').is_vertically_symmetric());
+        assert!(!Board::from_char_grid("a.a\nbcb", '.').is_vertically_symmetric());
+    }
+
+    #[test]
+    fn diff_works() {
+        let old = Board::from_char_grid("ab.\nc..", '.');
+        let new = Board::from_char_grid("ax.\n..d", '.');
+
+        assert_eq!(vec![
+            (Position::new(0, 1), Some(&'c'), None),
+            (Position::new(1, 0), Some(&'b'), Some(&'x')),
+            (Position::new(2, 1), None, Some(&'d')),
+        ], old.diff(&new));
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn largest_empty_rect_works() {
+        let board = Board::from_char_grid("#....\n#...#\n.#..#\n#....", '.');
+
+        assert_eq!(Some(Dimension::from_origin(Position::new(2, 0), 2, 4)), board.largest_empty_rect());
+    }
+
+    #[test]
+    fn largest_empty_rect_full_works() {
+        let board = Board::from_char_grid("##\n##", '.');
+
+        assert_eq!(None, board.largest_empty_rect());
+    }
+
+    #[test]
+    fn replace_value_works() {
+        let mut board = Board::from_char_grid("aba\n.ca", '.');
+
+        assert_eq!(3, board.replace_value(&'a', 'x'));
+        assert_eq!(vec![
+            vec![Some('x'), Some('b'), Some('x')],
+            vec![None, Some('c'), Some('x')],
+        ], board.as_grid());
+        assert_eq!(0, board.replace_value(&'a', 'x'));
+    }
+
+    #[test]
+    fn count_values_works() {
+        let mut board = Board::new(Dimension::new(3, 3));
+        board.set_field(Position::new(0, 0), 'a');
+        board.set_field(Position::new(1, 0), 'b');
+        board.set_field(Position::new(2, 0), 'a');
+        board.set_field(Position::new(1, 1), 'a');
+
+        let counts = board.count_values();
+        assert_eq!(2, counts.len());
+        assert_eq!(Some(&3), counts.get(&'a'));
+        assert_eq!(Some(&1), counts.get(&'b'));
+    }
+
+    #[test]
+    fn validate_works() {
+        let mut board = Board::from_char_grid("ab\n.c", '.');
+        assert_eq!(Ok(()), board.validate());
+
+        board.values.insert(Position::new(5, -1), 'x');
+        assert_eq!(
+            Err(String::from("Field at (5, -1) lies outside of Dimension(origin=(0, 0), size=2x2)")),
+            board.validate()
+        );
+    }
+
+    #[test]
+    fn convolve_box_blur_works() {
+        let board = Board::from_fn(Dimension::new(3, 3), |pos| Some((pos.y * 3 + pos.x + 1) as u8));
+        let kernel = Dimension::from_origin(Position::new(-1, -1), 3, 3).iter()
+            .map(|offset| (offset.x, offset.y, 1.0 / 9.0))
+            .collect::<Vec<_>>();
+
+        let blurred = board.convolve(&kernel, 0.0);
+
+        assert_eq!(9, blurred.count());
+        assert!((blurred.get_field(Position::new(1, 1)).unwrap() - 5.0).abs() < 1e-9);
+        assert!((blurred.get_field(Position::new(0, 0)).unwrap() - 12.0 / 9.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn csv_round_trip_works() {
+        let mut board = Board::new(Dimension::new(3, 2));
+        board.set_field(Position::new(0, 0), 1);
+        board.set_field(Position::new(2, 0), 23);
+        board.set_field(Position::new(1, 1), 4);
+
+        let csv = board.to_csv("");
+
+        assert_eq!("1,,23\n,4,\n", csv);
+        assert!(board == Board::from_csv(&csv, |cell| cell.parse().ok()));
+    }
+
+    #[test]
+    fn from_csv_trailing_newline_works() {
+        let board = Board::from_csv("1,2\n3,4\n", |cell| cell.parse().ok());
+
+        assert_eq!(vec![
+            vec![Some(1), Some(2)],
+            vec![Some(3), Some(4)],
+        ], board.as_grid());
+    }
+
+    #[test]
+    fn from_csv_crlf_works() {
+        let board = Board::from_csv("1,2\r\n3,4\r\n", |cell| cell.parse().ok());
+
+        assert_eq!(vec![
+            vec![Some(1), Some(2)],
+            vec![Some(3), Some(4)],
+        ], board.as_grid());
+    }
+
+    /// A trailing empty row of a board with width 1 should survive the round trip.
+    #[test]
+    fn csv_round_trip_single_column_works() {
+        let mut board = Board::new(Dimension::new(1, 2));
+        board.set_field(Position::new(0, 0), 1);
+
+        let csv = board.to_csv("");
+
+        assert_eq!("1\n\n", csv);
+        assert!(board == Board::from_csv(&csv, |cell| cell.parse().ok()));
+    }
+
+    #[test]
+    fn neighbors_mut_works() {
+        let mut board = Board::new_filled(Dimension::new(3, 3), 1);
+        board.clear_field(Position::new(1, 0));
+
+        let neighbors = board.neighbors_mut(Position::new(1, 1));
+        assert_eq!(3, neighbors.len());
+        neighbors.into_iter().for_each(|(_, val)| *val += 1);
+
+        assert_eq!(vec![
+            vec![Some(1), None, Some(1)],
+            vec![Some(2), Some(1), Some(2)],
+            vec![Some(1), Some(2), Some(1)],
+        ], board.as_grid());
+    }
+
+    /// Neighbors across the edges of a wrapping board should be returned once.
+    #[test]
+    fn neighbors_mut_wrapping_works() {
+        let mut board = Board::new_wrapping(Dimension::new(2, 3));
+        board.fill_region(Dimension::new(2, 3), 1);
+
+        let neighbors = board.neighbors_mut(Position::new(0, 0));
+        assert_eq!(vec![
+            Position::new(0, 2),
+            Position::new(1, 0),
+            Position::new(0, 1),
+        ], neighbors.iter().map(|(pos, _)| *pos).collect::<Vec<_>>());
+        neighbors.into_iter().for_each(|(_, val)| *val += 1);
+
+        assert_eq!(vec![
+            vec![Some(1), Some(2)],
+            vec![Some(2), Some(1)],
+            vec![Some(2), Some(1)],
+        ], board.as_grid());
+
+        let mut single = Board::new_wrapping(Dimension::new(1, 1));
+        single.set_field(Position::new(0, 0), 1);
+        assert_eq!(vec![(Position::new(0, 0), &mut 1)], single.neighbors_mut(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn iter_by_value_works() {
+        let board = Board::from_char_grid("cb\nab\n.a", '.');
+
+        assert_eq!(vec![
+            (Position::new(0, 1), &'a'),
+            (Position::new(1, 2), &'a'),
+            (Position::new(1, 0), &'b'),
+            (Position::new(1, 1), &'b'),
+            (Position::new(0, 0), &'c'),
+        ], board.iter_by_value());
+    }
+
+    #[test]
+    fn mirror_region_works() {
+        let mut board = Board::from_char_grid("ab..x\nc...x", '.');
+
+        board.mirror_region(Dimension::new(2, 2), Axis::Vertical);
+
+        assert!(board == Board::from_char_grid("ab.ba\nc...c", '.'));
+        assert!(board.is_horizontally_symmetric());
+    }
+
+    #[test]
+    fn mirror_region_horizontal_works() {
+        let mut board = Board::from_char_grid("ab\n..\n..", '.');
+
+        board.mirror_region(Dimension::new(2, 1), Axis::Horizontal);
+
+        assert!(board == Board::from_char_grid("ab\n..\nab", '.'));
+    }
+
+    #[test]
+    fn fill_region_works() {
+        let mut board = Board::from_char_grid("a...\n....\n...b", '.');
+
+        board.fill_region(Dimension::from_origin(Position::new(1, 1), 2, 5), 'x');
+
+        assert!(board == Board::from_char_grid("a...\n.xx.\n.xxb", '.'));
+        assert_eq!(Dimension::new(4, 3), board.dimension);
+    }
+
+    #[test]
+    fn clear_region_works() {
+        let mut board = Board::from_char_grid("abcd\nefgh\nijkl", '.');
+
+        assert_eq!(4, board.clear_region(Dimension::from_origin(Position::new(1, 1), 2, 5)));
+        assert!(board == Board::from_char_grid("abcd\ne..h\ni..l", '.'));
+        assert_eq!(0, board.clear_region(Dimension::from_origin(Position::new(1, 1), 2, 2)));
+    }
+
+    #[test]
+    fn checksum_works() {
+        let mut board = Board::from_char_grid("ab\n.c", '.');
+        let checksum = board.checksum();
+
+        board.map_in_place(|_, _| {});
+        board.shift_wrapping((2, 2));
+        assert_eq!(checksum, board.checksum());
+        assert_eq!(checksum, Board::from_char_grid("ab\n.c", '.').checksum());
+
+        board.set_field(Position::new(0, 1), 'd');
+        assert_ne!(checksum, board.checksum());
+    }
+
+    #[test]
+    fn iter_ring_center_works() {
+        let board = Board::from_char_grid("ab\ncd", '.');
+
+        assert_eq!(vec![(Position::new(1, 0), Some(&'b'))], board.iter_ring(Position::new(1, 0), 0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iter_ring_works() {
+        let board = Board::<char>::new(Dimension::new(5, 5));
+
+        assert_eq!(vec![
+            Position::new(1, 1),
+            Position::new(2, 1),
+            Position::new(3, 1),
+            Position::new(3, 2),
+            Position::new(3, 3),
+            Position::new(2, 3),
+            Position::new(1, 3),
+            Position::new(1, 2),
+        ], board.iter_ring(Position::new(2, 2), 1).map(|(pos, _)| pos).collect::<Vec<_>>());
+        assert_eq!(16, board.iter_ring(Position::new(2, 2), 2).count());
+    }
+
+    /// A huge radius should neither allocate nor walk the whole ring.
+    #[test]
+    fn iter_ring_huge_radius_works() {
+        let board = Board::<char>::new(Dimension::new(3, 3));
+
+        assert_eq!(0, board.iter_ring(Position::new(1, 1), 1 << 40).count());
+        assert_eq!(0, board.iter_ring(Position::new(1, 1), usize::MAX).count());
+        assert_eq!(vec![
+            Position::new(2, 2),
+            Position::new(1, 2),
+            Position::new(0, 2),
+        ], board.iter_ring(Position::new(1, 2 - (1 << 40)), 1 << 40).map(|(pos, _)| pos).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iter_ring_clipped_works() {
+        let board = Board::<char>::new(Dimension::new(3, 3));
+
+        assert_eq!(vec![
+            Position::new(1, 0),
+            Position::new(1, 1),
+            Position::new(0, 1),
+        ], board.iter_ring(Position::new(0, 0), 1).map(|(pos, _)| pos).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iter_spiral_works() {
+        let board = Board::from_char_grid("ab.\n.c.\n..d\n...", '.');
+        let center = Position::new(1, 1);
+
+        let spiral = board.iter_spiral(center).collect::<Vec<_>>();
+
+        assert_eq!(Some(&(center, Some(&'c'))), spiral.first());
+        assert_eq!(board.dimension.field_amount(), spiral.len());
+        assert_eq!(
+            board.positions().collect::<HashSet<_>>(),
+            spiral.iter().map(|(pos, _)| *pos).collect::<HashSet<_>>()
+        );
+        assert!(spiral[1..9].iter().all(|(pos, _)| pos.x.abs_diff(center.x).max(pos.y.abs_diff(center.y)) == 1));
+    }
+
+    #[test]
+    fn set_if_empty_works() {
+        let mut board = Board::from_char_grid("a.", '.');
+
+        assert!(board.set_if_empty(Position::new(1, 0), 'b'));
+        assert!(!board.set_if_empty(Position::new(0, 0), 'c'));
+        assert!(board == Board::from_char_grid("ab", '.'));
+    }
+
+    #[test]
+    fn set_if_empty_out_of_bounds_fails() {
+        let mut board = Board::from_char_grid("a.", '.');
+
+        assert!(!board.set_if_empty(Position::new(2, 0), 'b'));
+        assert_eq!(1, board.count());
+    }
+
+    #[test]
+    fn take_works() {
+        let mut board = Board::from_char_grid("ab", '.');
+
+        assert_eq!(Some('a'), board.take(Position::new(0, 0)));
+        assert_eq!(None, board.take(Position::new(0, 0)));
+        assert!(board == Board::from_char_grid(".b", '.'));
+    }
+
+    #[test]
+    fn replace_works() {
+        let mut board = Board::from_char_grid("a.", '.');
+
+        assert_eq!(Some('a'), board.replace(Position::new(0, 0), 'c'));
+        assert_eq!(None, board.replace(Position::new(1, 0), 'd'));
+        assert_eq!(None, board.replace(Position::new(2, 0), 'e'));
+        assert!(board == Board::from_char_grid("cd", '.'));
+    }
+
+    #[test]
+    fn row_and_column_counts_works() {
+        let board = Board::from_char_grid("a..\nbcd\n..e", '.');
+
+        assert_eq!(vec![1, 3, 1], board.row_counts());
+        assert_eq!(vec![2, 1, 2], board.column_counts());
+        assert_eq!(board.dimension.width(), board.row_counts()[1]);
+    }
+
+    #[test]
+    fn clear_full_rows_works() {
+        let mut board = Board::from_char_grid("a..\n.b.\ncde\n..f", '.');
+
+        assert_eq!(1, board.clear_full_rows());
+        assert!(board == Board::from_char_grid("...\na..\n.b.\n..f", '.'));
+        assert_eq!(0, board.clear_full_rows());
+    }
+
+    #[test]
+    fn diagonals_works() {
+        let board = Board::from_char_grid("abc\ndef\nghi", '.');
+        let values = |anti| board.diagonals(anti)
+            .map(|diagonal| diagonal.into_iter().filter_map(|(_, val)| val).collect::<String>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["c", "bf", "aei", "dh", "g"], values(false));
+        assert_eq!(vec!["a", "bd", "ceg", "fh", "i"], values(true));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_filled_works() {
+        use rayon::iter::ParallelIterator;
+
+        let board = Board::from_fn(Dimension::new(20, 20), |pos| Some(pos.x * pos.y));
+
+        let parallel_sum: isize = board.par_filled().map(|(_, val)| *val).sum();
+        let sequential_sum: isize = board.values.values().sum();
+
+        assert_eq!(sequential_sum, parallel_sum);
+        assert_eq!(board.count(), board.par_filled().count());
     }
 
     #[test]
@@ -0,0 +1,328 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::Board;
+use crate::position::Position;
+
+impl<T> Board<T> {
+    /// Find the cheapest path from start to goal using Dijkstra's algorithm.
+    ///
+    /// Movement is orthogonal and stays inside the dimension of this board. The closure
+    /// returns the cost to enter a field, or None if the field is impassable. The start
+    /// field itself costs nothing. Return the path (including start and goal) and its
+    /// total cost, or None if the goal cannot be reached. Paths whose total cost would
+    /// exceed u32::MAX are not taken.
+    pub fn dijkstra<F: Fn(Position, Option<&T>) -> Option<u32>>(&self, start: Position, goal: Position, cost: F) -> Option<(Vec<Position>, u32)> {
+        self.cheapest_path(start, goal, cost, |_, _| 0)
+    }
+
+    /// Find the cheapest path from start to goal using the A* algorithm.
+    ///
+    /// The cost closure works like the one of #[Board::dijkstra]. The heuristic estimates the
+    /// remaining cost from a position to the goal. If it never overestimates (like the
+    /// manhattan distance with a minimal cost of 1), the found path is guaranteed to be the
+    /// cheapest one. Return the path (including start and goal), or None if the goal cannot be reached.
+    pub fn a_star<C, H>(&self, start: Position, goal: Position, cost: C, heuristic: H) -> Option<Vec<Position>>
+        where C: Fn(Position, Option<&T>) -> Option<u32>,
+              H: Fn(Position, Position) -> u32 {
+        self.cheapest_path(start, goal, cost, heuristic).map(|(path, _)| path)
+    }
+
+    /// Return the distance from every reachable field to the nearest seed, using
+    /// an orthogonal breadth-first search through passable fields.
+    ///
+    /// Seeds always have the distance 0, seeds outside of this board are ignored.
+    pub fn distance_field<F: Fn(Position, Option<&T>) -> bool>(&self, seeds: &[Position], passable: F) -> HashMap<Position, u32> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        for seed in seeds.iter().copied().filter(|pos| self.dimension.contains_position(*pos)) {
+            distances.insert(seed, 0);
+            queue.push_back(seed);
+        }
+
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current] + 1;
+
+            for neighbor in current.orthogonal_neighbors().iter().copied() {
+                if self.dimension.contains_position(neighbor)
+                    && !distances.contains_key(&neighbor)
+                    && passable(neighbor, self.values.get(&neighbor)) {
+                    distances.insert(neighbor, distance);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Return the next position on a shortest orthogonal path from one position to another
+    /// through passable fields, or None if the target cannot be reached or is already reached.
+    pub fn step_toward<F: Fn(Position, Option<&T>) -> bool>(&self, from: Position, to: Position, passable: F) -> Option<Position> {
+        if from == to {
+            return None;
+        }
+
+        let distances = self.distance_field(&[to], passable);
+
+        from.orthogonal_neighbors().iter()
+            .copied()
+            .filter_map(|pos| distances.get(&pos).map(|distance| (*distance, pos)))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, pos)| pos)
+    }
+
+    /// Search the cheapest path with a priority queue, ordered by the cost so far plus the heuristic.
+    /// With a heuristic which is always zero, this is Dijkstra's algorithm.
+    fn cheapest_path<C, H>(&self, start: Position, goal: Position, cost: C, heuristic: H) -> Option<(Vec<Position>, u32)>
+        where C: Fn(Position, Option<&T>) -> Option<u32>,
+              H: Fn(Position, Position) -> u32 {
+        if !self.dimension.contains_position(start) || !self.dimension.contains_position(goal) {
+            return None;
+        }
+
+        let mut queue = BinaryHeap::new();
+        let mut costs = HashMap::new();
+        let mut predecessors = HashMap::new();
+        queue.push(Reverse((heuristic(start, goal), 0, start)));
+        costs.insert(start, 0);
+
+        while let Some(Reverse((_, current_cost, current))) = queue.pop() {
+            if current == goal {
+                return Some((Self::reconstruct_path(&predecessors, goal), current_cost));
+            }
+
+            if costs.get(&current).is_some_and(|c| *c < current_cost) {
+                continue;
+            }
+
+            for neighbor in current.orthogonal_neighbors().iter().copied() {
+                if !self.dimension.contains_position(neighbor) {
+                    continue;
+                }
+
+                let next_cost = match cost(neighbor, self.values.get(&neighbor)).and_then(|c| current_cost.checked_add(c)) {
+                    None => continue,
+                    Some(c) => c
+                };
+
+                if costs.get(&neighbor).is_none_or(|c| next_cost < *c) {
+                    costs.insert(neighbor, next_cost);
+                    predecessors.insert(neighbor, current);
+                    queue.push(Reverse((next_cost.saturating_add(heuristic(neighbor, goal)), next_cost, neighbor)));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Follow the predecessors back from the goal and return the path in walking order.
+    fn reconstruct_path(predecessors: &HashMap<Position, Position>, goal: Position) -> Vec<Position> {
+        let mut path = vec![goal];
+        let mut current = goal;
+
+        while let Some(previous) = predecessors.get(&current) {
+            path.push(*previous);
+            current = *previous
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Board;
+    use crate::dimension::Dimension;
+    use crate::position::Position;
+
+    /// With uniform cost, the path should be as long as the shortest walk.
+    #[test]
+    fn dijkstra_uniform_cost_works() {
+        let board = Board::<char>::new(Dimension::new(5, 5));
+        let start = Position::new(0, 0);
+        let goal = Position::new(3, 4);
+
+        let (path, cost) = board.dijkstra(start, goal, |_, _| Some(1)).unwrap();
+
+        assert_eq!(7, cost);
+        assert_eq!(8, path.len());
+        assert_eq!(Some(&start), path.first());
+        assert_eq!(Some(&goal), path.last());
+    }
+
+    /// The path should take a longer detour if the direct way is more expensive.
+    #[test]
+    fn dijkstra_cheaper_detour_works() {
+        // s~g
+        // ...
+        let mut board = Board::new(Dimension::new(3, 2));
+        board.set_field(Position::new(1, 0), '~');
+
+        let cost = |_, val: Option<&char>| match val {
+            Some('~') => Some(10),
+            _ => Some(1)
+        };
+        let (path, cost) = board.dijkstra(Position::new(0, 0), Position::new(2, 0), cost).unwrap();
+
+        assert_eq!(4, cost);
+        assert_eq!(vec![
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(1, 1),
+            Position::new(2, 1),
+            Position::new(2, 0),
+        ], path);
+    }
+
+    #[test]
+    fn dijkstra_unreachable_works() {
+        // s#g
+        // .#.
+        let mut board = Board::new(Dimension::new(3, 2));
+        board.set_field(Position::new(1, 0), '#');
+        board.set_field(Position::new(1, 1), '#');
+
+        let cost = |_, val: Option<&char>| match val {
+            Some('#') => None,
+            _ => Some(1)
+        };
+
+        assert_eq!(None, board.dijkstra(Position::new(0, 0), Position::new(2, 0), cost));
+    }
+
+    /// Paths whose cost would overflow are skipped instead of panicking.
+    #[test]
+    fn dijkstra_large_cost_works() {
+        let board = Board::<char>::new(Dimension::new(3, 1));
+
+        assert_eq!(None, board.dijkstra(Position::new(0, 0), Position::new(2, 0), |_, _| Some(u32::MAX)));
+        assert_eq!(
+            Some(u32::MAX - 1),
+            board.dijkstra(Position::new(0, 0), Position::new(2, 0), |_, _| Some(u32::MAX / 2)).map(|(_, cost)| cost)
+        );
+    }
+
+    /// A* should find a path as cheap as the one found by Dijkstra.
+    #[test]
+    fn a_star_works() {
+        // ....
+        // .##.
+        // s#g.
+        let mut board = Board::new(Dimension::new(4, 3));
+        board.set_field(Position::new(1, 1), '#');
+        board.set_field(Position::new(2, 1), '#');
+        board.set_field(Position::new(1, 2), '#');
+
+        let cost = |_, val: Option<&char>| match val {
+            Some('#') => None,
+            _ => Some(1)
+        };
+        let start = Position::new(0, 2);
+        let goal = Position::new(2, 2);
+
+        let (dijkstra_path, _) = board.dijkstra(start, goal, cost).unwrap();
+        let a_star_path = board.a_star(start, goal, cost, |pos, goal| pos.manhattan_distance(goal) as u32).unwrap();
+
+        assert_eq!(dijkstra_path.len(), a_star_path.len());
+        assert_eq!(9, a_star_path.len());
+        assert_eq!(Some(&goal), a_star_path.last());
+    }
+
+    /// A huge heuristic should not overflow the priority of a field.
+    #[test]
+    fn a_star_large_heuristic_works() {
+        let board = Board::<char>::new(Dimension::new(3, 1));
+
+        let path = board.a_star(Position::new(0, 0), Position::new(2, 0), |_, _| Some(1), |_, _| u32::MAX).unwrap();
+
+        assert_eq!(3, path.len());
+    }
+
+    /// An admissible heuristic should not change the length of the found path.
+    #[test]
+    fn a_star_admissible_heuristic_works() {
+        let board = Board::<char>::new(Dimension::new(6, 6));
+        let start = Position::new(0, 5);
+        let goal = Position::new(4, 1);
+
+        let with_heuristic = board.a_star(start, goal, |_, _| Some(1), |pos, goal| pos.manhattan_distance(goal) as u32).unwrap();
+        let without_heuristic = board.a_star(start, goal, |_, _| Some(1), |_, _| 0).unwrap();
+
+        assert_eq!(9, with_heuristic.len());
+        assert_eq!(without_heuristic.len(), with_heuristic.len());
+    }
+
+    /// On an open board, the distance to a single seed is the manhattan distance.
+    #[test]
+    fn distance_field_works() {
+        let dimension = Dimension::new(4, 3);
+        let board = Board::<char>::new(dimension);
+        let seed = Position::new(1, 1);
+
+        let distances = board.distance_field(&[seed], |_, _| true);
+
+        assert_eq!(dimension.field_amount(), distances.len());
+        assert!(dimension.iter().all(|pos| distances[&pos] as usize == pos.manhattan_distance(seed)));
+    }
+
+    #[test]
+    fn distance_field_obstacle_works() {
+        // s#.
+        // .#.
+        // ...
+        let mut board = Board::new(Dimension::new(3, 3));
+        board.set_field(Position::new(1, 0), '#');
+        board.set_field(Position::new(1, 1), '#');
+
+        let distances = board.distance_field(&[Position::new(0, 0)], |_, val| val.is_none());
+
+        assert_eq!(7, distances.len());
+        assert_eq!(None, distances.get(&Position::new(1, 0)));
+        assert_eq!(Some(&5), distances.get(&Position::new(2, 1)));
+        assert_eq!(Some(&6), distances.get(&Position::new(2, 0)));
+    }
+
+    #[test]
+    fn distance_field_multiple_seeds_works() {
+        let board = Board::<char>::new(Dimension::new(5, 1));
+
+        let distances = board.distance_field(&[Position::new(0, 0), Position::new(4, 0)], |_, _| true);
+
+        assert_eq!(Some(&1), distances.get(&Position::new(3, 0)));
+        assert_eq!(Some(&2), distances.get(&Position::new(2, 0)));
+    }
+
+    #[test]
+    fn step_toward_works() {
+        let board = Board::<char>::new(Dimension::new(5, 1));
+
+        assert_eq!(Some(Position::new(1, 0)), board.step_toward(Position::new(0, 0), Position::new(4, 0), |_, _| true));
+        assert_eq!(None, board.step_toward(Position::new(2, 0), Position::new(2, 0), |_, _| true));
+    }
+
+    #[test]
+    fn step_toward_detour_works() {
+        // s#g
+        // ...
+        let mut board = Board::new(Dimension::new(3, 2));
+        board.set_field(Position::new(1, 0), '#');
+
+        let passable = |_, val: Option<&char>| val.is_none();
+
+        assert_eq!(Some(Position::new(0, 1)), board.step_toward(Position::new(0, 0), Position::new(2, 0), passable));
+        assert_eq!(Some(Position::new(1, 1)), board.step_toward(Position::new(0, 1), Position::new(2, 0), passable));
+    }
+
+    #[test]
+    fn step_toward_unreachable_works() {
+        // s#g
+        let mut board = Board::new(Dimension::new(3, 1));
+        board.set_field(Position::new(1, 0), '#');
+
+        assert_eq!(None, board.step_toward(Position::new(0, 0), Position::new(2, 0), |_, val| val.is_none()));
+    }
+}
@@ -1,3 +1,6 @@
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::num::TryFromIntError;
 use std::ops::{Add, Neg, Sub};
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
@@ -15,6 +18,211 @@ impl Position {
     pub fn new_u(x: usize, y: usize) -> Self {
         Self::new(x as isize, y as isize)
     }
+
+    /// Return the coordinates of this position as usize values.
+    /// Return None if any coordinate is negative.
+    pub fn try_into_usize(&self) -> Option<(usize, usize)> {
+        <(usize, usize)>::try_from(*self).ok()
+    }
+
+    /// Add the given delta to this position.
+    /// Return None if any coordinate would overflow.
+    pub fn checked_add(&self, rhs: (isize, isize)) -> Option<Position> {
+        Some(Position::new(self.x.checked_add(rhs.0)?, self.y.checked_add(rhs.1)?))
+    }
+
+    /// Add the given delta to this position.
+    /// Coordinates which would overflow are clamped to the bounds of isize.
+    pub fn saturating_add(&self, rhs: (isize, isize)) -> Position {
+        Position::new(self.x.saturating_add(rhs.0), self.y.saturating_add(rhs.1))
+    }
+
+    /// Rotate this position by a quarter turn clockwise around the given pivot.
+    ///
+    /// The rotation maps (x, y) relative to the pivot to (y, -x). "Clockwise" assumes the
+    /// y-axis points upwards. On a board, where it points downwards, this turns counterclockwise
+    /// like #[Position::turn_left], which is the opposite direction of #[crate::Board::rotate_90].
+    pub fn rotate_cw(&self, pivot: Position) -> Position {
+        let relative = self - &pivot;
+        Position::new(relative.y, -relative.x) + pivot
+    }
+
+    /// Rotate this position by a quarter turn counterclockwise around the given pivot.
+    ///
+    /// The rotation maps (x, y) relative to the pivot to (-y, x). "Counterclockwise" assumes the
+    /// y-axis points upwards. On a board, where it points downwards, this turns clockwise
+    /// like #[Position::turn_right] and #[crate::Board::rotate_90].
+    pub fn rotate_ccw(&self, pivot: Position) -> Position {
+        let relative = self - &pivot;
+        Position::new(-relative.y, relative.x) + pivot
+    }
+
+    /// Turn this position, interpreted as a direction, by 90 degrees to the left.
+    /// With the y-axis pointing downwards, (1, 0) (right) turns into (0, -1) (up).
+    pub fn turn_left(&self) -> Position {
+        Position::new(self.y, -self.x)
+    }
+
+    /// Turn this position, interpreted as a direction, by 90 degrees to the right.
+    /// With the y-axis pointing downwards, (1, 0) (right) turns into (0, 1) (down).
+    pub fn turn_right(&self) -> Position {
+        Position::new(-self.y, self.x)
+    }
+
+    /// Linearly interpolate between this and the other position at the fraction num / den,
+    /// using integer math only.
+    ///
+    /// Every coordinate is computed as self + (other - self) * num / den, where the
+    /// division rounds toward zero. Panics if den is zero.
+    pub fn lerp(&self, other: Position, num: isize, den: isize) -> Position {
+        let delta = other - *self;
+        Position::new(self.x + delta.x * num / den, self.y + delta.y * num / den)
+    }
+
+    /// Return the manhattan distance between this and the other position.
+    pub fn manhattan_distance(&self, other: Position) -> usize {
+        ((self.x - other.x).abs() + (self.y - other.y).abs()) as usize
+    }
+
+    /// Return if the other position shares an edge with this one.
+    pub fn is_orthogonally_adjacent(&self, other: Position) -> bool {
+        self.manhattan_distance(other) == 1
+    }
+
+    /// Return if the other position only shares a corner with this one.
+    pub fn is_diagonally_adjacent(&self, other: Position) -> bool {
+        (self.x - other.x).abs() == 1 && (self.y - other.y).abs() == 1
+    }
+
+    /// Return an iterator over all positions with a manhattan distance of at most 'radius'
+    /// to this one, which form a diamond. The positions are ordered row by row.
+    /// A radius above isize::MAX is treated as isize::MAX.
+    pub fn within_manhattan(&self, radius: usize) -> impl Iterator<Item = Position> {
+        let center = *self;
+        let radius = isize::try_from(radius).unwrap_or(isize::MAX);
+
+        (-radius..=radius).flat_map(move |dy| {
+            let width = radius - dy.abs();
+            (-width..=width).map(move |dx| center + (dx, dy))
+        })
+    }
+
+    /// Return an iterator over all positions on the line from this to the other
+    /// position (both inclusive), computed with Bresenham's algorithm.
+    pub fn line_to(&self, other: Position) -> LineIterator {
+        LineIterator::new(*self, other)
+    }
+
+    /// Return the four orthogonal neighbors of this position, regardless of any bounds.
+    ///
+    /// The order is (x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y).
+    pub fn orthogonal_neighbors(&self) -> [Position; 4] {
+        [
+            Position::new(self.x, self.y - 1),
+            Position::new(self.x + 1, self.y),
+            Position::new(self.x, self.y + 1),
+            Position::new(self.x - 1, self.y),
+        ]
+    }
+
+    /// Return all eight neighbors of this position (including the diagonal ones),
+    /// regardless of any bounds.
+    ///
+    /// The neighbors are ordered row by row, from (x - 1, y - 1) to (x + 1, y + 1).
+    pub fn diagonal_neighbors(&self) -> [Position; 8] {
+        [
+            Position::new(self.x - 1, self.y - 1),
+            Position::new(self.x, self.y - 1),
+            Position::new(self.x + 1, self.y - 1),
+            Position::new(self.x - 1, self.y),
+            Position::new(self.x + 1, self.y),
+            Position::new(self.x - 1, self.y + 1),
+            Position::new(self.x, self.y + 1),
+            Position::new(self.x + 1, self.y + 1),
+        ]
+    }
+}
+
+/// Iterator over the positions on a line between two positions,
+/// following Bresenham's line algorithm.
+pub struct LineIterator {
+    current_position: Option<Position>,
+    end: Position,
+    dx: isize,
+    dy: isize,
+    step: Position,
+    error: isize,
+}
+
+impl LineIterator {
+    pub fn new(start: Position, end: Position) -> Self {
+        let dx = (end.x - start.x).abs();
+        let dy = -(end.y - start.y).abs();
+
+        LineIterator {
+            current_position: Some(start),
+            end,
+            dx,
+            dy,
+            step: Position::new((end.x - start.x).signum(), (end.y - start.y).signum()),
+            error: dx + dy,
+        }
+    }
+}
+
+impl Iterator for LineIterator {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current_position?;
+
+        if current == self.end {
+            self.current_position = None;
+            return Some(current);
+        }
+
+        let mut next = current;
+        let doubled_error = 2 * self.error;
+
+        if doubled_error >= self.dy {
+            self.error += self.dy;
+            next.x += self.step.x;
+        }
+
+        if doubled_error <= self.dx {
+            self.error += self.dx;
+            next.y += self.step.y;
+        }
+
+        self.current_position = Some(next);
+        Some(current)
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(isize, isize)> for Position {
+    fn from((x, y): (isize, isize)) -> Self {
+        Position::new(x, y)
+    }
+}
+
+impl From<(usize, usize)> for Position {
+    fn from((x, y): (usize, usize)) -> Self {
+        Position::new_u(x, y)
+    }
+}
+
+impl TryFrom<Position> for (usize, usize) {
+    type Error = TryFromIntError;
+
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        Ok((usize::try_from(position.x)?, usize::try_from(position.y)?))
+    }
 }
 
 impl Add for Position {
@@ -99,6 +307,8 @@ impl Sub for &Position {
 
 #[cfg(test)]
 mod tests {
+    use std::convert::TryFrom;
+
     use crate::position::Position;
 
     #[test]
@@ -106,6 +316,49 @@ mod tests {
         assert_eq!(Position::new(1, 2) + Position::new(2, 3), Position::new(3, 5))
     }
 
+    #[test]
+    fn display_works() {
+        assert_eq!("(-1, 2)", Position::new(-1, 2).to_string());
+    }
+
+    #[test]
+    fn from_isize_tuple_works() {
+        assert_eq!(Position::new(-1, 2), Position::from((-1isize, 2isize)));
+    }
+
+    #[test]
+    fn from_usize_tuple_works() {
+        let position: Position = (1usize, 2usize).into();
+        assert_eq!(Position::new(1, 2), position);
+        assert_eq!(Position::new(isize::MAX, 0), Position::from((isize::MAX as usize, 0usize)));
+    }
+
+    #[test]
+    fn try_into_usize_works() {
+        assert_eq!(Some((3, 4)), Position::new(3, 4).try_into_usize());
+        assert_eq!(None, Position::new(3, -4).try_into_usize());
+        assert_eq!(None, Position::new(-3, 4).try_into_usize());
+    }
+
+    #[test]
+    fn try_from_position_works() {
+        assert_eq!(Ok((0, 2)), <(usize, usize)>::try_from(Position::new(0, 2)));
+        assert!(<(usize, usize)>::try_from(Position::new(-1, 2)).is_err());
+    }
+
+    #[test]
+    fn checked_add_works() {
+        assert_eq!(Some(Position::new(isize::MAX, 0)), Position::new(isize::MAX - 1, 1).checked_add((1, -1)));
+        assert_eq!(None, Position::new(isize::MAX, 0).checked_add((1, 0)));
+        assert_eq!(None, Position::new(0, isize::MIN).checked_add((0, -1)));
+    }
+
+    #[test]
+    fn saturating_add_works() {
+        assert_eq!(Position::new(isize::MAX, 1), Position::new(isize::MAX - 1, 0).saturating_add((5, 1)));
+        assert_eq!(Position::new(0, isize::MIN), Position::new(0, isize::MIN + 1).saturating_add((0, -5)));
+    }
+
     #[test]
     fn neg_works() {
         assert_eq!(-Position::new(1, 2), Position::new(-1, -2))
@@ -117,6 +370,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn cmp_works() {
         let zero_zero = Position::new(0, 0);
         let zero_one = Position::new(0, 1);
@@ -128,4 +382,191 @@ mod tests {
         assert_eq!(true, zero_zero < one_zero);
         assert_eq!(true, zero_zero < one_one);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn rotate_around_origin_works() {
+        let origin = Position::default();
+        let start = Position::new(1, 0);
+
+        let mut cw = start;
+        let mut ccw = start;
+        for _ in 0..4 {
+            cw = cw.rotate_cw(origin);
+            ccw = ccw.rotate_ccw(origin);
+        }
+
+        assert_eq!(Position::new(0, -1), start.rotate_cw(origin));
+        assert_eq!(Position::new(0, 1), start.rotate_ccw(origin));
+        assert_eq!(start, cw);
+        assert_eq!(start, ccw);
+    }
+
+    #[test]
+    fn rotate_around_pivot_works() {
+        let pivot = Position::new(2, 3);
+        let position = Position::new(4, 3);
+
+        assert_eq!(Position::new(2, 1), position.rotate_cw(pivot));
+        assert_eq!(Position::new(2, 5), position.rotate_ccw(pivot));
+        assert_eq!(position, position.rotate_cw(pivot).rotate_ccw(pivot));
+    }
+
+    #[test]
+    fn turn_left_works() {
+        let right = Position::new(1, 0);
+        let headings = [right.turn_left(), right.turn_left().turn_left(), right.turn_left().turn_left().turn_left()];
+
+        assert_eq!([Position::new(0, -1), Position::new(-1, 0), Position::new(0, 1)], headings);
+        assert_eq!(right, headings[2].turn_left());
+    }
+
+    #[test]
+    fn turn_right_works() {
+        let right = Position::new(1, 0);
+        let headings = [right.turn_right(), right.turn_right().turn_right(), right.turn_right().turn_right().turn_right()];
+
+        assert_eq!([Position::new(0, 1), Position::new(-1, 0), Position::new(0, -1)], headings);
+        assert_eq!(right, headings[2].turn_right());
+    }
+
+    #[test]
+    fn orthogonal_neighbors_works() {
+        assert_eq!([
+            Position::new(0, -1),
+            Position::new(1, 0),
+            Position::new(0, 1),
+            Position::new(-1, 0),
+        ], Position::new(0, 0).orthogonal_neighbors());
+
+        assert_eq!([
+            Position::new(-2, -4),
+            Position::new(-1, -3),
+            Position::new(-2, -2),
+            Position::new(-3, -3),
+        ], Position::new(-2, -3).orthogonal_neighbors());
+    }
+
+    #[test]
+    fn diagonal_neighbors_works() {
+        assert_eq!([
+            Position::new(-1, -1),
+            Position::new(0, -1),
+            Position::new(1, -1),
+            Position::new(-1, 0),
+            Position::new(1, 0),
+            Position::new(-1, 1),
+            Position::new(0, 1),
+            Position::new(1, 1),
+        ], Position::new(0, 0).diagonal_neighbors());
+
+        assert_eq!([
+            Position::new(-3, -4),
+            Position::new(-2, -4),
+            Position::new(-1, -4),
+            Position::new(-3, -3),
+            Position::new(-1, -3),
+            Position::new(-3, -2),
+            Position::new(-2, -2),
+            Position::new(-1, -2),
+        ], Position::new(-2, -3).diagonal_neighbors());
+    }
+
+    #[test]
+    fn lerp_works() {
+        let start = Position::new(0, 2);
+        let end = Position::new(4, -3);
+
+        assert_eq!(start, start.lerp(end, 0, 2));
+        assert_eq!(end, start.lerp(end, 2, 2));
+        assert_eq!(Position::new(2, 0), start.lerp(end, 1, 2));
+        assert_eq!(Position::new(1, 1), start.lerp(end, 1, 4));
+    }
+
+    #[test]
+    fn manhattan_distance_works() {
+        assert_eq!(0, Position::new(1, 1).manhattan_distance(Position::new(1, 1)));
+        assert_eq!(5, Position::new(-1, 2).manhattan_distance(Position::new(1, -1)));
+    }
+
+    #[test]
+    fn is_orthogonally_adjacent_works() {
+        let pos = Position::new(2, -1);
+
+        assert!(pos.is_orthogonally_adjacent(Position::new(2, 0)));
+        assert!(pos.is_orthogonally_adjacent(Position::new(1, -1)));
+        assert!(!pos.is_orthogonally_adjacent(Position::new(3, 0)));
+        assert!(!pos.is_orthogonally_adjacent(pos));
+        assert!(!pos.is_orthogonally_adjacent(Position::new(4, -1)));
+    }
+
+    #[test]
+    fn is_diagonally_adjacent_works() {
+        let pos = Position::new(2, -1);
+
+        assert!(pos.is_diagonally_adjacent(Position::new(3, 0)));
+        assert!(pos.is_diagonally_adjacent(Position::new(1, -2)));
+        assert!(!pos.is_diagonally_adjacent(Position::new(2, 0)));
+        assert!(!pos.is_diagonally_adjacent(pos));
+        assert!(!pos.is_diagonally_adjacent(Position::new(4, 1)));
+    }
+
+    #[test]
+    fn within_manhattan_works() {
+        let center = Position::new(1, -1);
+
+        assert_eq!(vec![center], center.within_manhattan(0).collect::<Vec<_>>());
+        assert_eq!(vec![
+            Position::new(1, -2),
+            Position::new(0, -1),
+            Position::new(1, -1),
+            Position::new(2, -1),
+            Position::new(1, 0),
+        ], center.within_manhattan(1).collect::<Vec<_>>());
+
+        let radius_two = center.within_manhattan(2).collect::<Vec<_>>();
+        assert_eq!(13, radius_two.len());
+        assert!(radius_two.iter().all(|pos| pos.manhattan_distance(center) <= 2));
+        assert!(radius_two.contains(&Position::new(3, -1)));
+        assert!(!radius_two.contains(&Position::new(3, 0)));
+    }
+
+    #[test]
+    fn within_manhattan_huge_radius_works() {
+        assert_eq!(Some(Position::new(0, -isize::MAX)), Position::new(0, 0).within_manhattan(usize::MAX).next());
+    }
+
+    #[test]
+    fn line_to_horizontal_works() {
+        assert_eq!(vec![
+            Position::new(3, 1),
+            Position::new(2, 1),
+            Position::new(1, 1),
+            Position::new(0, 1),
+        ], Position::new(3, 1).line_to(Position::new(0, 1)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn line_to_steep_works() {
+        assert_eq!(vec![
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(1, 2),
+            Position::new(1, 3),
+        ], Position::new(0, 0).line_to(Position::new(1, 3)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn line_to_diagonal_works() {
+        assert_eq!(vec![
+            Position::new(0, 0),
+            Position::new(-1, 1),
+            Position::new(-2, 2),
+        ], Position::new(0, 0).line_to(Position::new(-2, 2)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn line_to_single_position_works() {
+        let position = Position::new(2, 2);
+        assert_eq!(vec![position], position.line_to(position).collect::<Vec<_>>())
+    }
+}